@@ -4,4 +4,5 @@ pub mod container {
 
 pub mod prng {
     pub use tc_prng::*;
+    pub use tc_prng_derive::TcRandom as Random;
 }
@@ -0,0 +1,28 @@
+//! `#[derive(Random)]` through the `tc` facade, without naming `tc_prng`.
+
+use tc::prng::{prelude::*, Random};
+
+#[derive(Random, Debug, PartialEq)]
+struct Point {
+    x: u8,
+    #[random(range = 1..=6)]
+    roll: u32,
+}
+
+#[derive(Random, Debug, PartialEq)]
+enum Coin {
+    Heads,
+    Tails,
+}
+
+#[test]
+fn derives_through_tc() {
+    let a: Point = xorshiro(3_u64).next();
+    let b: Point = xorshiro(3_u64).next();
+    assert_eq!(a, b);
+    assert!((1..=6).contains(&a.roll));
+
+    let mut rng = xorshiro(3_u32);
+    let flips: Vec<Coin> = (0..64).map(|_| rng.next()).collect();
+    assert!(flips.contains(&Coin::Heads) && flips.contains(&Coin::Tails));
+}
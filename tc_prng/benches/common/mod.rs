@@ -0,0 +1,31 @@
+//! Timing harness shared by the benchmarks.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+pub const WORDS: usize = 1 << 20;
+pub const ROUNDS: u32 = 50;
+
+/// Times `fill` on a buffer of [`WORDS`] values and prints the best of
+/// [`ROUNDS`] runs.
+pub fn bench<T: Copy + Default>(name: &str, mut fill: impl FnMut(&mut [T])) {
+    let mut buf = vec![T::default(); WORDS];
+    fill(&mut buf); // warm up
+
+    let mut best = Duration::MAX;
+    for _ in 0..ROUNDS {
+        let start = Instant::now();
+        fill(black_box(&mut buf));
+        best = best.min(start.elapsed());
+    }
+    black_box(&buf);
+
+    let bytes = (WORDS * std::mem::size_of::<T>()) as f64;
+    println!(
+        "{name:<20} {:>8.2} GB/s {:>8.2} ns/value",
+        bytes / best.as_secs_f64() / 1e9,
+        best.as_nanos() as f64 / WORDS as f64
+    );
+}
@@ -0,0 +1,43 @@
+//! Throughput of the scalar generators against the multi-lane variants.
+//!
+//! Run with `cargo bench -p tc_prng --bench lanes`.
+
+mod common;
+
+use common::{bench, ROUNDS, WORDS};
+use tc_prng::{lanes::*, prelude::*};
+
+fn main() {
+    println!("u64 fill, {WORDS} values, best of {ROUNDS}");
+    let mut rng = split_mix(1_u64);
+    bench("SplitMix64", |b: &mut [u64]| {
+        b.iter_mut().for_each(|v| *v = rng.next())
+    });
+    let mut rng = SplitMix64x4::new(1);
+    bench("SplitMix64x4", |b: &mut [u64]| rng.fill(b));
+    let mut rng = SplitMix64x8::new(1);
+    bench("SplitMix64x8", |b: &mut [u64]| rng.fill(b));
+    let mut rng = xorshiro(1_u64);
+    bench("Xorshiro64", |b: &mut [u64]| {
+        b.iter_mut().for_each(|v| *v = rng.next())
+    });
+    let mut rng = Xorshiro64x4::new(1);
+    bench("Xorshiro64x4", |b: &mut [u64]| rng.fill(b));
+    let mut rng = Xorshiro64x8::new(1);
+    bench("Xorshiro64x8", |b: &mut [u64]| rng.fill(b));
+
+    println!();
+    println!("f32 fill, {WORDS} values, best of {ROUNDS}");
+    let mut rng = split_mix(1_u64);
+    bench("SplitMix64", |b: &mut [f32]| {
+        b.iter_mut().for_each(|v| *v = rng.next())
+    });
+    let mut rng = SplitMix64x8::new(1);
+    bench("SplitMix64x8", |b: &mut [f32]| rng.fill_f32(b));
+    let mut rng = xorshiro(1_u64);
+    bench("Xorshiro64", |b: &mut [f32]| {
+        b.iter_mut().for_each(|v| *v = rng.next())
+    });
+    let mut rng = Xorshiro64x8::new(1);
+    bench("Xorshiro64x8", |b: &mut [f32]| rng.fill_f32(b));
+}
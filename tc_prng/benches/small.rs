@@ -0,0 +1,38 @@
+//! Throughput of the small-state generators against `Xorshiro64`.
+//!
+//! Run with `cargo bench -p tc_prng --bench small`.
+
+mod common;
+
+use common::{ROUNDS, WORDS};
+use tc_prng::{jsf::*, prelude::*, romu::*, sfc::*, wyrand::WyRand};
+
+fn bench<G: PrngCore>(name: &str, mut rng: G)
+where
+    G::Word: Copy + Default,
+{
+    common::bench(name, |b: &mut [G::Word]| {
+        b.iter_mut().for_each(|v| *v = rng.calc())
+    });
+}
+
+fn main() {
+    println!("64-bit words, {WORDS} per fill, best of {ROUNDS}");
+    bench("Xorshiro64", xorshiro(1_u64));
+    bench("SplitMix64", split_mix(1_u64));
+    bench("WyRand", WyRand::new(1));
+    bench("Sfc64", Sfc64::new(1));
+    bench("Jsf64", Jsf64::new(1));
+    bench("RomuQuad", RomuQuad::new(1));
+    bench("RomuTrio", RomuTrio::new(1));
+    bench("RomuDuo", RomuDuo::new(1));
+    bench("RomuDuoJr", RomuDuoJr::new(1));
+
+    println!();
+    println!("32-bit words, {WORDS} per fill, best of {ROUNDS}");
+    bench("Xorshiro32", xorshiro(1_u32));
+    bench("Sfc32", Sfc32::new(1));
+    bench("Jsf32", Jsf32::new(1));
+    bench("RomuQuad32", RomuQuad32::new(1));
+    bench("RomuTrio32", RomuTrio32::new(1));
+}
@@ -0,0 +1,180 @@
+//! Bit-level draws that use every bit of a generator word.
+//!
+//! # Consumption order
+//!
+//! [`BitSource`] holds one word of its generator at a time and hands its bits
+//! out least significant first. A request for `n` bits takes the next `n`
+//! bits in that order, the first one becoming the value's lowest bit; when
+//! the cached word runs out part way, the remaining bits come from the low
+//! end of the next word. A new word is drawn only when a bit is needed and
+//! none is left, so the sequence of values depends only on the generator and
+//! the sequence of requests.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tc_prng::{bits::BitSource, prelude::*};
+//!
+//! let mut bits = BitSource::new(xorshiro(5_u64));
+//! let alive = bits.next_bit(); // 1 of 64 bits
+//! let dir = bits.next_bits(2); // 0..4, 2 more bits
+//! let tile = bits.next_below(6); // 0..6, 3 bits per attempt
+//! assert_eq!(bits.bits_left(), 64 - 1 - 2 - 3);
+//! # let _ = (alive, dir, tile);
+//! ```
+
+use crate::PrngCore;
+
+/// Hands out the bits of a generator's words one request at a time.
+#[derive(Clone, Debug)]
+pub struct BitSource<G> {
+    rng: G,
+    buf: u128,
+    left: u32,
+}
+
+impl<G> BitSource<G>
+where
+    G: PrngCore,
+    G::Word: Into<u128>,
+{
+    const WORD_BITS: u32 = std::mem::size_of::<G::Word>() as u32 * 8;
+
+    pub fn new(rng: G) -> Self {
+        Self {
+            rng,
+            buf: 0,
+            left: 0,
+        }
+    }
+
+    /// Bits remaining in the cached word.
+    pub fn bits_left(&self) -> u32 {
+        self.left
+    }
+
+    pub fn inner(&self) -> &G {
+        &self.rng
+    }
+
+    /// Returns the generator. Unused cached bits are dropped.
+    pub fn into_inner(self) -> G {
+        self.rng
+    }
+
+    pub fn next_bit(&mut self) -> bool {
+        if self.left == 0 {
+            self.refill();
+        }
+        let bit = self.buf & 1 == 1;
+        self.buf >>= 1;
+        self.left -= 1;
+        bit
+    }
+
+    /// The next `n` bits as an integer, `n <= 64`.
+    pub fn next_bits(&mut self, n: u32) -> u64 {
+        assert!(n <= 64, "at most 64 bits per request");
+        let mut value = 0;
+        let mut got = 0;
+        while got < n {
+            if self.left == 0 {
+                self.refill();
+            }
+            let take = (n - got).min(self.left);
+            // `take` is at most 64, so neither shift overflows.
+            value |= ((self.buf & ((1 << take) - 1)) as u64) << got;
+            self.buf >>= take;
+            self.left -= take;
+            got += take;
+        }
+        value
+    }
+
+    /// A uniform integer in `0..n`.
+    ///
+    /// Draws `ceil(log2(n))` bits and retries while the value is `n` or more,
+    /// so each attempt succeeds with probability above one half.
+    pub fn next_below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "cannot sample empty range");
+        let bits = 64 - (n - 1).leading_zeros();
+        loop {
+            let v = self.next_bits(bits);
+            if v < n {
+                return v;
+            }
+        }
+    }
+
+    fn refill(&mut self) {
+        self.buf = self.rng.calc().into();
+        self.left = Self::WORD_BITS;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{mock::ScriptedRng, prelude::*};
+
+    #[test]
+    fn consumption_order() {
+        let mut bits = BitSource::new(ScriptedRng::new(vec![0b1011_u32, 0xFFFF_FFFF]));
+        assert!(bits.next_bit());
+        assert_eq!(bits.next_bits(3), 0b101);
+        assert_eq!(bits.bits_left(), 28);
+
+        // 28 zero bits from the first word, then 4 ones from the second.
+        assert_eq!(bits.next_bits(32), 0xF000_0000);
+        assert_eq!(bits.bits_left(), 28);
+        assert_eq!(bits.inner().consumed(), 2);
+    }
+
+    #[test]
+    fn refills_only_when_empty() {
+        let mut bits = BitSource::new(ScriptedRng::new(vec![u64::MAX]));
+        for _ in 0..64 {
+            assert!(bits.next_bit());
+        }
+        assert_eq!(bits.next_bits(0), 0);
+        assert_eq!(bits.into_inner().remaining(), 0);
+    }
+
+    #[test]
+    fn wide_words() {
+        let mut bits = BitSource::new(ScriptedRng::new(vec![u128::MAX << 64]));
+        assert_eq!(bits.next_bits(64), 0);
+        assert_eq!(bits.next_bits(64), u64::MAX);
+        assert_eq!(bits.bits_left(), 0);
+    }
+
+    #[test]
+    fn bounded_values() {
+        // 6 needs 3 bits; 7 and 6 are rejected, 5 accepted.
+        let mut bits = BitSource::new(ScriptedRng::new(vec![0b101_110_111_u32]));
+        assert_eq!(bits.next_below(6), 5);
+        assert_eq!(bits.next_below(1), 0);
+        assert_eq!(bits.bits_left(), 23);
+
+        let mut bits = BitSource::new(xorshiro(3_u64));
+        let mut counts = [0; 6];
+        for _ in 0..6000 {
+            counts[bits.next_below(6) as usize] += 1;
+        }
+        assert!(counts.iter().all(|c| (850..1150).contains(c)), "{counts:?}");
+    }
+
+    #[test]
+    fn dump_value_types() {
+        let mut bits = BitSource::new(split_mix(123456_u64));
+
+        let a = bits.next_bit();
+        assert!(a);
+
+        let b = bits.next_bits(40);
+        assert_eq!(b, 827833108852);
+
+        let c = bits.next_below(1000);
+        assert_eq!(c, 813);
+    }
+}
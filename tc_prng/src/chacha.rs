@@ -0,0 +1,382 @@
+use crate::{
+    entropy::os_entropy,
+    state::{Algorithm, Codec, Persist, Reader, StateError},
+    EntropyError, Prng, PrngCore, Seedable,
+};
+
+/// ChaCha stream cipher used as a cryptographically secure generator.
+///
+/// Unlike `SplitMix` and `Xorshiro`, observing any amount of output does not
+/// reveal the key or the rest of the stream, which makes it suitable for
+/// tokens, session ids and competitive shuffles. `ROUNDS` is 8, 12 or 20;
+/// use the [`ChaCha8`], [`ChaCha12`] and [`ChaCha20`] aliases.
+///
+/// It is deliberately not `Copy`: an implicit copy would hand out the same
+/// keystream twice. Use `clone()` where that is really wanted.
+///
+/// The state follows Bernstein's original layout: a 256-bit key, a 64-bit
+/// block counter and a 64-bit stream id. Output is produced one 16 word
+/// block at a time and handed out word by word.
+///
+/// # Examples
+///
+/// ```rust
+/// use tc_prng::chacha::ChaCha20;
+///
+/// let mut rng = ChaCha20::with_stream([7; 32], 1);
+/// let mut token = [0u8; 16];
+/// rng.fill_bytes(&mut token);
+/// let roll: u32 = rng.next();
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct ChaCha<const ROUNDS: usize> {
+    key: [u32; 8],
+    counter: u64,
+    stream: u64,
+    buf: [u32; 16],
+    idx: usize,
+}
+
+/// Position of a [`ChaCha`] generator, see [`Persist`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ChaChaState {
+    pub key: [u8; 32],
+    pub stream: u64,
+    pub word_pos: u128,
+}
+
+pub type ChaCha8 = ChaCha<8>;
+pub type ChaCha12 = ChaCha<12>;
+pub type ChaCha20 = ChaCha<20>;
+
+impl<const ROUNDS: usize> Default for ChaCha<ROUNDS> {
+    fn default() -> Self {
+        Self::new([0; 32])
+    }
+}
+
+impl<const ROUNDS: usize> Seedable for ChaCha<ROUNDS> {
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(seed)
+    }
+
+    /// Keys the generator from [`os_entropy`]. Unlike the other generators
+    /// there is no clock-based fallback: a key that can be guessed is no
+    /// key, so a failing OS source is an error.
+    fn from_entropy() -> Result<Self, EntropyError> {
+        let mut key = [0; 32];
+        os_entropy(&mut key)?;
+        Ok(Self::new(key))
+    }
+}
+
+impl<const ROUNDS: usize> Persist for ChaCha<ROUNDS> {
+    type State = ChaChaState;
+
+    const ALGORITHM: Algorithm = Algorithm::ChaCha;
+    const VARIANT: u8 = ROUNDS as u8;
+    const STATE_LEN: usize = 32 + 8 + 16;
+
+    fn state(&self) -> ChaChaState {
+        let mut key = [0; 32];
+        for (b, w) in key.chunks_exact_mut(4).zip(self.key) {
+            b.copy_from_slice(&w.to_le_bytes());
+        }
+        ChaChaState {
+            key,
+            stream: self.stream,
+            word_pos: self.word_pos(),
+        }
+    }
+
+    fn from_state(state: ChaChaState) -> Self {
+        let mut rng = Self::with_stream(state.key, state.stream);
+        rng.set_word_pos(state.word_pos);
+        rng
+    }
+}
+
+impl<const ROUNDS: usize> Codec for ChaCha<ROUNDS> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        let state = self.state();
+        out.extend_from_slice(&state.key);
+        out.extend_from_slice(&state.stream.to_le_bytes());
+        out.extend_from_slice(&state.word_pos.to_le_bytes());
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, StateError> {
+        let mut r = Reader(&bytes[32..]);
+        Ok(Self::from_state(ChaChaState {
+            key: bytes[..32].try_into().unwrap(),
+            stream: r.u64(),
+            word_pos: r.u128(),
+        }))
+    }
+}
+
+impl<const ROUNDS: usize> ChaCha<ROUNDS> {
+    const CONSTANTS: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574];
+
+    pub fn new(key: [u8; 32]) -> Self {
+        Self::with_stream(key, 0)
+    }
+
+    pub fn with_stream(key: [u8; 32], stream: u64) -> Self {
+        assert!(
+            ROUNDS > 0 && ROUNDS.is_multiple_of(2),
+            "ChaCha rounds must be even"
+        );
+        let mut k = [0; 8];
+        for (w, b) in k.iter_mut().zip(key.chunks_exact(4)) {
+            *w = u32::from_le_bytes([b[0], b[1], b[2], b[3]]);
+        }
+        Self {
+            key: k,
+            counter: 0,
+            stream,
+            buf: [0; 16],
+            idx: 16,
+        }
+    }
+
+    pub fn stream(&self) -> u64 {
+        self.stream
+    }
+
+    /// Switches to another stream, keeping the current word position.
+    pub fn set_stream(&mut self, stream: u64) {
+        let pos = self.word_pos();
+        self.stream = stream;
+        self.set_word_pos(pos);
+    }
+
+    /// Index of the next word in the stream.
+    pub fn word_pos(&self) -> u128 {
+        // `counter` already points past the buffered block.
+        ((self.counter as u128) << 4).wrapping_sub(16 - self.idx as u128) & ((1 << 68) - 1)
+    }
+
+    /// Seeks to word `pos` of the current stream. Positions wrap at 2^68.
+    pub fn set_word_pos(&mut self, pos: u128) {
+        self.counter = (pos >> 4) as u64;
+        self.idx = (pos & 15) as usize;
+        if self.idx != 0 {
+            self.refill();
+        } else {
+            self.idx = 16;
+        }
+    }
+
+    /// Fills `dst` with keystream bytes, consuming whole words; the unused
+    /// bytes of a trailing partial word are discarded.
+    pub fn fill_bytes(&mut self, dst: &mut [u8]) {
+        let mut chunks = dst.chunks_exact_mut(4);
+        for c in &mut chunks {
+            c.copy_from_slice(&self.calc().to_le_bytes());
+        }
+        let rest = chunks.into_remainder();
+        if !rest.is_empty() {
+            let w = self.calc().to_le_bytes();
+            rest.copy_from_slice(&w[..rest.len()]);
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<V>(&mut self) -> V
+    where
+        Self: Prng<V>,
+    {
+        Prng::next_val(self)
+    }
+
+    /// Generates the block for the current counter and advances it.
+    fn refill(&mut self) {
+        self.buf = Self::block(&self.key, self.counter, self.stream);
+        self.counter = self.counter.wrapping_add(1);
+    }
+
+    fn block(key: &[u32; 8], counter: u64, stream: u64) -> [u32; 16] {
+        let mut input = [0; 16];
+        input[..4].copy_from_slice(&Self::CONSTANTS);
+        input[4..12].copy_from_slice(key);
+        input[12] = counter as u32;
+        input[13] = (counter >> 32) as u32;
+        input[14] = stream as u32;
+        input[15] = (stream >> 32) as u32;
+
+        let mut x = input;
+        for _ in 0..ROUNDS / 2 {
+            // Column round
+            quarter(&mut x, 0, 4, 8, 12);
+            quarter(&mut x, 1, 5, 9, 13);
+            quarter(&mut x, 2, 6, 10, 14);
+            quarter(&mut x, 3, 7, 11, 15);
+            // Diagonal round
+            quarter(&mut x, 0, 5, 10, 15);
+            quarter(&mut x, 1, 6, 11, 12);
+            quarter(&mut x, 2, 7, 8, 13);
+            quarter(&mut x, 3, 4, 9, 14);
+        }
+
+        for (o, i) in x.iter_mut().zip(input) {
+            *o = o.wrapping_add(i);
+        }
+        x
+    }
+}
+
+#[inline]
+fn quarter(x: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    x[a] = x[a].wrapping_add(x[b]);
+    x[d] = (x[d] ^ x[a]).rotate_left(16);
+    x[c] = x[c].wrapping_add(x[d]);
+    x[b] = (x[b] ^ x[c]).rotate_left(12);
+    x[a] = x[a].wrapping_add(x[b]);
+    x[d] = (x[d] ^ x[a]).rotate_left(8);
+    x[c] = x[c].wrapping_add(x[d]);
+    x[b] = (x[b] ^ x[c]).rotate_left(7);
+}
+
+impl<const ROUNDS: usize> PrngCore for ChaCha<ROUNDS> {
+    type Word = u32;
+
+    fn calc(&mut self) -> u32 {
+        if self.idx == 16 {
+            self.refill();
+            self.idx = 0;
+        }
+        let v = self.buf[self.idx];
+        self.idx += 1;
+        v
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn keystream<const R: usize>(rng: &mut ChaCha<R>) -> Vec<u8> {
+        let mut out = vec![0; 64];
+        rng.fill_bytes(&mut out);
+        out
+    }
+
+    #[test]
+    fn rfc8439_block_function() {
+        // Section 2.3.2: nonce 00:00:00:09:00:00:00:4a:00:00:00:00, counter 1.
+        let key: Vec<u8> = (0..32).collect();
+        let mut rng = ChaCha20::with_stream(key.try_into().unwrap(), 0x4a000000);
+        rng.set_word_pos((1 | (0x09000000 << 32)) << 4);
+        assert_eq!(
+            keystream(&mut rng),
+            hex(concat!(
+                "10f1e7e4d13b5915500fdd1fa32071c4c7d1f4c733c068030422aa9ac3d46c4e",
+                "d2826446079faa0914c2d705d98b02a2b5129cd1de164eb9cbd083e8a2503c4e"
+            ))
+        );
+    }
+
+    #[test]
+    fn rfc8439_keystream_vectors() {
+        // Appendix A.1, test vectors #1 and #2.
+        let mut rng = ChaCha20::new([0; 32]);
+        assert_eq!(
+            keystream(&mut rng),
+            hex(concat!(
+                "76b8e0ada0f13d90405d6ae55386bd28bdd219b8a08ded1aa836efcc8b770dc7",
+                "da41597c5157488d7724e03fb8d84a376a43b8f41518a11cc387b669b2ee6586"
+            ))
+        );
+        assert_eq!(
+            keystream(&mut rng),
+            hex(concat!(
+                "9f07e7be5551387a98ba977c732d080dcb0f29a048e3656912c6533e32ee7aed",
+                "29b721769ce64e43d57133b074d839d531ed1f28510afb45ace10a1f4b794d6f"
+            ))
+        );
+
+        // Appendix A.1, test vector #3.
+        let mut key = [0; 32];
+        key[31] = 1;
+        let mut rng = ChaCha20::new(key);
+        rng.set_word_pos(16);
+        assert_eq!(
+            keystream(&mut rng),
+            hex(concat!(
+                "3aeb5224ecf849929b9d828db1ced4dd832025e8018b8160b82284f3c949aa5a",
+                "8eca00bbb4a73bdad192b5c42f73f2fd4e273644c8b36125a64addeb006c13a0"
+            ))
+        );
+    }
+
+    #[test]
+    fn reduced_rounds() {
+        // All-zero key and nonce (draft-strombergson-chacha-test-vectors TC1).
+        assert_eq!(
+            keystream(&mut ChaCha8::new([0; 32])),
+            hex(concat!(
+                "3e00ef2f895f40d67f5bb8e81f09a5a12c840ec3ce9a7f3b181be188ef711a1e",
+                "984ce172b9216f419f445367456d5619314a42a3da86b001387bfdb80e0cfe42"
+            ))
+        );
+        assert_eq!(
+            keystream(&mut ChaCha12::new([0; 32])),
+            hex(concat!(
+                "9bf49a6a0755f953811fce125f2683d50429c3bb49e074147e0089a52eae155f",
+                "0564f879d27ae3c02ce82834acfa8c793a629f2ca0de6919610be82f411326be"
+            ))
+        );
+    }
+
+    #[test]
+    fn word_position() {
+        let mut rng = ChaCha12::with_stream([3; 32], 9);
+        let words: Vec<u32> = (0..40).map(|_| rng.next()).collect();
+        assert_eq!(rng.word_pos(), 40);
+
+        for (n, w) in words.iter().enumerate() {
+            let mut r = ChaCha12::with_stream([3; 32], 9);
+            r.set_word_pos(n as u128);
+            assert_eq!(r.word_pos(), n as u128);
+            assert_eq!(r.next::<u32>(), *w);
+        }
+
+        rng.set_stream(10);
+        assert_eq!(rng.word_pos(), 40);
+        assert_ne!(rng.next::<u32>(), words[0]);
+    }
+
+    #[test]
+    fn keyed_from_os_entropy() {
+        let a = ChaCha20::from_entropy().unwrap();
+        let b = ChaCha20::from_entropy().unwrap();
+        assert_ne!(a.state().key, b.state().key);
+        assert_eq!(a.state().word_pos, 0);
+    }
+
+    #[test]
+    fn dump_value_types() {
+        let mut rng = ChaCha20::new([1; 32]);
+
+        let a: u8 = rng.next();
+        assert_eq!(a, 32);
+
+        let b: u128 = rng.next();
+        assert_eq!(b, 272101575744123776223214778145102505018);
+
+        let c: bool = rng.next();
+        assert!(!c);
+
+        let d: f32 = rng.next();
+        assert_eq!(d, 0.3748167);
+    }
+}
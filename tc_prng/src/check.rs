@@ -0,0 +1,843 @@
+//! Property testing with integrated shrinking.
+//!
+//! A [`Gen`] builds values from the choices of a [`Source`]. A random case
+//! draws the choices from a generator and records them; shrinking replays
+//! edited recordings, deleting choices and moving them toward 0. Every
+//! combinator therefore shrinks without extra code, and smaller choices give
+//! simpler values: shorter collections, numbers closer to zero, earlier
+//! alternatives of [`one_of`]. Shrinking is greedy: it stops once no single
+//! deletion or smaller choice still fails, which is not always the smallest
+//! failing input overall.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tc_prng::check::{self, forall};
+//!
+//! forall(check::vec(check::any::<u32>(), 0..=20), |mut v: Vec<u32>| {
+//!     v.sort();
+//!     v.windows(2).all(|w| w[0] <= w[1])
+//! });
+//! ```
+//!
+//! A failing property panics with the smallest input found and the seed of
+//! the case. Running the test with that seed in [`SEED_VAR`] runs only that
+//! case again, in every property without an explicit [`Check::seed`]:
+//!
+//! ```text
+//! TC_PRNG_CHECK_SEED=0x9e3779b97f4a7c15 cargo test sorts_slices
+//! ```
+
+use std::{
+    cell::Cell,
+    fmt::{self, Debug},
+    ops::{Range, RangeInclusive},
+    panic::{self, AssertUnwindSafe},
+    rc::Rc,
+    sync::Once,
+};
+
+use crate::{split_mix::SplitMix64, xorshiro, xorshiro::Xorshiro64, PrngCore};
+
+/// Environment variable holding a case seed to run instead of random cases.
+pub const SEED_VAR: &str = "TC_PRNG_CHECK_SEED";
+
+/// Attempts of [`Gen::filter`] before the case is rejected.
+const FILTER_TRIES: usize = 100;
+
+/// The choices a [`Gen`] builds values from.
+pub struct Source {
+    /// `None` when replaying `choices`.
+    rng: Option<Xorshiro64>,
+    choices: Vec<u64>,
+    record: Vec<u64>,
+    rejected: bool,
+}
+
+impl Source {
+    fn random(seed: u64) -> Self {
+        Self {
+            rng: Some(xorshiro(seed)),
+            choices: Vec::new(),
+            record: Vec::new(),
+            rejected: false,
+        }
+    }
+
+    /// Replays `choices`, reducing each into the range asked for. Once they
+    /// run out every choice is 0.
+    fn replay(choices: &[u64]) -> Self {
+        Self {
+            rng: None,
+            choices: choices.to_vec(),
+            record: Vec::new(),
+            rejected: false,
+        }
+    }
+
+    /// A choice in `0..n`, or any `u64` when `n` is 0. Shrinking moves
+    /// choices toward 0, so map 0 to the simplest value.
+    pub fn choice(&mut self, n: u64) -> u64 {
+        let c = match &mut self.rng {
+            Some(rng) if n == 0 => rng.next(),
+            Some(rng) => rng.next_range(0..n),
+            None => {
+                let c = self.choices.get(self.record.len()).copied().unwrap_or(0);
+                if n == 0 {
+                    c
+                } else {
+                    c % n
+                }
+            }
+        };
+        self.record.push(c);
+        c
+    }
+
+    /// Marks the case as invalid, e.g. when no value passes a filter. The
+    /// runner skips rejected cases and shrinking never ends on one.
+    pub fn reject(&mut self) {
+        self.rejected = true;
+    }
+}
+
+/// A strategy for generating values of `T`.
+pub struct Gen<T>(Rc<dyn Fn(&mut Source) -> T>);
+
+impl<T> Clone for Gen<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: 'static> Gen<T> {
+    pub fn new(f: impl Fn(&mut Source) -> T + 'static) -> Self {
+        Self(Rc::new(f))
+    }
+
+    pub fn generate(&self, src: &mut Source) -> T {
+        (self.0)(src)
+    }
+
+    pub fn map<U: 'static>(self, f: impl Fn(T) -> U + 'static) -> Gen<U> {
+        Gen::new(move |src| f(self.generate(src)))
+    }
+
+    /// Keeps the values `pred` accepts. The case is rejected when 100
+    /// values in a row are not.
+    pub fn filter(self, pred: impl Fn(&T) -> bool + 'static) -> Gen<T> {
+        Gen::new(move |src| {
+            let mut value = self.generate(src);
+            for _ in 1..FILTER_TRIES {
+                if pred(&value) {
+                    return value;
+                }
+                value = self.generate(src);
+            }
+            if !pred(&value) {
+                src.reject();
+            }
+            value
+        })
+    }
+
+    /// Generates a value, then a value from the strategy `f` makes of it.
+    pub fn flat_map<U: 'static>(self, f: impl Fn(T) -> Gen<U> + 'static) -> Gen<U> {
+        Gen::new(move |src| f(self.generate(src)).generate(src))
+    }
+
+    pub fn zip<U: 'static>(self, other: Gen<U>) -> Gen<(T, U)> {
+        Gen::new(move |src| (self.generate(src), other.generate(src)))
+    }
+}
+
+/// Types with a default strategy, see [`any`].
+pub trait Arbitrary: Sized + 'static {
+    fn arbitrary() -> Gen<Self>;
+}
+
+/// The default strategy of `T`.
+///
+/// Integers cover their whole range and shrink toward 0, floats are in
+/// `[0, 1)` like `next`, collections hold up to 16 elements and strings
+/// are printable ASCII.
+pub fn any<T: Arbitrary>() -> Gen<T> {
+    T::arbitrary()
+}
+
+/// Always `value`.
+pub fn just<T: Clone + 'static>(value: T) -> Gen<T> {
+    Gen::new(move |_| value.clone())
+}
+
+/// Values from `range`, see [`GenRange`].
+pub fn range<T, R: GenRange<T>>(range: R) -> Gen<T> {
+    range.gen()
+}
+
+/// Ranges [`range`] accepts.
+///
+/// Integer ranges shrink toward the value closest to 0, float ranges toward
+/// their start.
+pub trait GenRange<T> {
+    fn gen(self) -> Gen<T>;
+}
+
+/// A choice in `0..=max`.
+fn choice_to(src: &mut Source, max: u128) -> u128 {
+    match u64::try_from(max + 1) {
+        Ok(n) => src.choice(n) as u128,
+        Err(_) => src.choice(0) as u128,
+    }
+}
+
+/// An integer in `lo..=hi`, shrinking toward 0 or the bound closest to it.
+fn int_in(src: &mut Source, lo: i128, hi: i128) -> i128 {
+    if lo >= 0 {
+        lo + choice_to(src, (hi - lo) as u128) as i128
+    } else if hi <= 0 {
+        hi - choice_to(src, (hi - lo) as u128) as i128
+    } else {
+        // Magnitude first, then the sign, so both shrink to 0.
+        let m = choice_to(src, hi.max(-lo) as u128) as i128;
+        let negative = src.choice(2) == 1;
+        if (negative && m <= -lo) || m > hi {
+            -m
+        } else {
+            m
+        }
+    }
+}
+
+macro_rules! impl_int {
+    ($($t:ty),*) => {$(
+        impl GenRange<$t> for RangeInclusive<$t> {
+            fn gen(self) -> Gen<$t> {
+                let (lo, hi) = self.into_inner();
+                assert!(lo <= hi, "cannot generate from an empty range");
+                Gen::new(move |src| int_in(src, lo as i128, hi as i128) as $t)
+            }
+        }
+
+        impl GenRange<$t> for Range<$t> {
+            fn gen(self) -> Gen<$t> {
+                assert!(self.start < self.end, "cannot generate from an empty range");
+                (self.start..=self.end - 1).gen()
+            }
+        }
+
+        impl Arbitrary for $t {
+            fn arbitrary() -> Gen<Self> {
+                range(<$t>::MIN..=<$t>::MAX)
+            }
+        }
+    )*};
+}
+
+impl_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl Arbitrary for u128 {
+    fn arbitrary() -> Gen<Self> {
+        Gen::new(|src| {
+            let h = src.choice(0) as u128;
+            (h << 64) | src.choice(0) as u128
+        })
+    }
+}
+
+impl Arbitrary for i128 {
+    fn arbitrary() -> Gen<Self> {
+        // Zigzag, so small magnitudes come from small choices.
+        any::<u128>().map(|z| (z >> 1) as i128 ^ -((z & 1) as i128))
+    }
+}
+
+macro_rules! impl_float {
+    ($($t:ty => $bits:expr),*) => {$(
+        impl GenRange<$t> for Range<$t> {
+            fn gen(self) -> Gen<$t> {
+                let Range { start, end } = self;
+                assert!(start < end, "cannot generate from an empty range");
+                assert!(
+                    start.is_finite() && end.is_finite(),
+                    "cannot generate from an infinite range"
+                );
+                Gen::new(move |src| {
+                    let u = src.choice(1 << $bits) as $t / (1_u64 << $bits) as $t;
+                    let v = crate::range::lerp!(start, end, u);
+                    // Rounding can land on `end` for wide ranges.
+                    if v < end {
+                        v
+                    } else {
+                        start
+                    }
+                })
+            }
+        }
+
+        impl Arbitrary for $t {
+            fn arbitrary() -> Gen<Self> {
+                range(0.0..1.0)
+            }
+        }
+    )*};
+}
+
+impl_float!(f32 => 24, f64 => 53);
+
+impl Arbitrary for bool {
+    fn arbitrary() -> Gen<Self> {
+        Gen::new(|src| src.choice(2) == 1)
+    }
+}
+
+impl Arbitrary for char {
+    fn arbitrary() -> Gen<Self> {
+        const SURROGATES: u64 = 0xe000 - 0xd800;
+        Gen::new(|src| {
+            let n = src.choice(0x11_0000 - SURROGATES);
+            let n = if n < 0xd800 { n } else { n + SURROGATES };
+            char::from_u32(n as u32).expect("surrogates are skipped")
+        })
+    }
+}
+
+impl Arbitrary for String {
+    fn arbitrary() -> Gen<Self> {
+        string(0..=16)
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Vec<T> {
+    fn arbitrary() -> Gen<Self> {
+        vec(any(), 0..=16)
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Option<T> {
+    fn arbitrary() -> Gen<Self> {
+        let some = any::<T>();
+        Gen::new(move |src| (src.choice(2) == 1).then(|| some.generate(src)))
+    }
+}
+
+macro_rules! impl_tuple {
+    ($(($($t:ident),+)),*) => {$(
+        impl<$($t: Arbitrary),+> Arbitrary for ($($t,)+) {
+            fn arbitrary() -> Gen<Self> {
+                Gen::new(|src| ($(any::<$t>().generate(src),)+))
+            }
+        }
+    )*};
+}
+
+impl_tuple!((A), (A, B), (A, B, C), (A, B, C, D));
+
+/// Vectors of `elem` with a length in `len`.
+///
+/// Each element past the minimum is preceded by a choice to continue, so
+/// shrinking can drop elements anywhere in the vector.
+pub fn vec<T: 'static>(elem: Gen<T>, len: RangeInclusive<usize>) -> Gen<Vec<T>> {
+    let (min, max) = len.into_inner();
+    assert!(min <= max, "cannot generate from an empty range");
+    Gen::new(move |src| {
+        let mut v = Vec::new();
+        // Continue with probability 7/8 past the minimum.
+        while v.len() < max && (v.len() < min || src.choice(8) != 0) {
+            v.push(elem.generate(src));
+        }
+        v
+    })
+}
+
+/// Printable ASCII, ordered so shrinking gives lowercase letters first.
+const ASCII: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789 \
+    !\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+
+/// A printable ASCII character, shrinking toward `'a'`.
+pub fn ascii() -> Gen<char> {
+    Gen::new(|src| ASCII[src.choice(ASCII.len() as u64) as usize] as char)
+}
+
+/// Printable ASCII strings with a length in `len`.
+pub fn string(len: RangeInclusive<usize>) -> Gen<String> {
+    vec(ascii(), len).map(|chars| chars.into_iter().collect())
+}
+
+/// A value from one of `gens`, each picked equally often. Shrinks toward
+/// the first.
+///
+/// # Panics
+///
+/// If `gens` is empty.
+pub fn one_of<T: 'static>(gens: Vec<Gen<T>>) -> Gen<T> {
+    assert!(!gens.is_empty(), "one_of needs a strategy");
+    Gen::new(move |src| gens[src.choice(gens.len() as u64) as usize].generate(src))
+}
+
+/// A value from one of `gens`, picked in proportion to its weight. Shrinks
+/// toward the first.
+///
+/// # Panics
+///
+/// If the weights add up to 0.
+pub fn weighted<T: 'static>(gens: Vec<(u32, Gen<T>)>) -> Gen<T> {
+    let total: u64 = gens.iter().map(|&(w, _)| w as u64).sum();
+    assert!(total > 0, "weighted needs a non-zero weight");
+    Gen::new(move |src| {
+        let mut pick = src.choice(total);
+        for (w, gen) in &gens {
+            if pick < *w as u64 {
+                return gen.generate(src);
+            }
+            pick -= *w as u64;
+        }
+        unreachable!("pick is below the total weight")
+    })
+}
+
+/// What a property returns: `true`, `()` or `Ok(())` pass. A panic fails
+/// the property too.
+pub trait Outcome {
+    fn into_result(self) -> Result<(), String>;
+}
+
+impl Outcome for bool {
+    fn into_result(self) -> Result<(), String> {
+        if self {
+            Ok(())
+        } else {
+            Err("property returned false".into())
+        }
+    }
+}
+
+impl Outcome for () {
+    fn into_result(self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl<E: Debug> Outcome for Result<(), E> {
+    fn into_result(self) -> Result<(), String> {
+        self.map_err(|e| format!("{e:?}"))
+    }
+}
+
+/// A property that failed, with the smallest input shrinking found.
+#[derive(Clone, Debug)]
+pub struct Failure<T> {
+    /// Seed of the failing case, for [`SEED_VAR`].
+    pub seed: u64,
+    pub original: T,
+    pub minimal: T,
+    /// Steps that made the input smaller.
+    pub shrinks: u32,
+    /// Why `minimal` fails.
+    pub message: String,
+}
+
+impl<T: Debug> fmt::Display for Failure<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "property failed: {}", self.message)?;
+        writeln!(f, "  minimal input:  {:?}", self.minimal)?;
+        writeln!(f, "  original input: {:?}", self.original)?;
+        writeln!(f, "  shrunk {} times", self.shrinks)?;
+        write!(f, "  reproduce with {SEED_VAR}={:#x}", self.seed)
+    }
+}
+
+/// Runs `prop` on `value`, turning a panic into a failure.
+fn evaluate<T, O: Outcome>(prop: &impl Fn(T) -> O, value: T) -> Result<(), String> {
+    match panic::catch_unwind(AssertUnwindSafe(|| prop(value))) {
+        Ok(outcome) => outcome.into_result(),
+        Err(payload) => Err(if let Some(s) = payload.downcast_ref::<&str>() {
+            s.to_string()
+        } else if let Some(s) = payload.downcast_ref::<String>() {
+            s.clone()
+        } else {
+            "property panicked".into()
+        }),
+    }
+}
+
+thread_local! {
+    /// Set while shrinking, when most property calls are expected to panic.
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` without printing the panic messages of this thread.
+fn quietly<R>(f: impl FnOnce() -> R) -> R {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.with(Cell::get) {
+                hook(info);
+            }
+        }));
+    });
+
+    struct Restore(bool);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            QUIET.with(|q| q.set(self.0));
+        }
+    }
+    let _restore = Restore(QUIET.with(|q| q.replace(true)));
+    f()
+}
+
+/// Whether choices `a` are simpler than `b`: fewer, or the same number and
+/// lexicographically smaller. Every accepted step is simpler, so shrinking
+/// ends.
+fn simpler(a: &[u64], b: &[u64]) -> bool {
+    a.len() < b.len() || (a.len() == b.len() && a < b)
+}
+
+struct Shrinker<'a, T, P> {
+    gen: &'a Gen<T>,
+    prop: &'a P,
+    best: Vec<u64>,
+    message: String,
+    shrinks: u32,
+    calls: u32,
+    max_calls: u32,
+}
+
+impl<T: 'static, O: Outcome, P: Fn(T) -> O> Shrinker<'_, T, P> {
+    /// Replays `candidate` and keeps it if it is simpler and still fails.
+    fn try_choices(&mut self, candidate: &[u64]) -> bool {
+        if self.calls >= self.max_calls {
+            return false;
+        }
+        let mut src = Source::replay(candidate);
+        let value = self.gen.generate(&mut src);
+        if src.rejected || !simpler(&src.record, &self.best) {
+            return false;
+        }
+        self.calls += 1;
+        match evaluate(self.prop, value) {
+            Ok(()) => false,
+            Err(message) => {
+                self.best = src.record;
+                self.message = message;
+                self.shrinks += 1;
+                true
+            }
+        }
+    }
+
+    fn shrink(&mut self) {
+        loop {
+            let before = self.shrinks;
+            for size in [8, 4, 2, 1] {
+                self.delete_chunks(size);
+                self.zero_chunks(size);
+            }
+            self.minimize_choices();
+            if self.shrinks == before || self.calls >= self.max_calls {
+                return;
+            }
+        }
+    }
+
+    fn delete_chunks(&mut self, size: usize) {
+        let mut i = 0;
+        while i + size <= self.best.len() {
+            let mut candidate = self.best.clone();
+            candidate.drain(i..i + size);
+            if !self.try_choices(&candidate) {
+                i += 1;
+            }
+        }
+    }
+
+    fn zero_chunks(&mut self, size: usize) {
+        let mut i = 0;
+        while i + size <= self.best.len() {
+            if self.best[i..i + size].iter().any(|&c| c != 0) {
+                let mut candidate = self.best.clone();
+                candidate[i..i + size].fill(0);
+                self.try_choices(&candidate);
+            }
+            i += 1;
+        }
+    }
+
+    /// Binary searches each choice for the smallest value that still fails.
+    fn minimize_choices(&mut self) {
+        let mut i = 0;
+        while i < self.best.len() {
+            let (mut lo, mut hi) = (0, self.best[i]);
+            while lo < hi {
+                let mid = lo + (hi - lo) / 2;
+                let mut candidate = self.best.clone();
+                candidate[i] = mid;
+                if !self.try_choices(&candidate) {
+                    lo = mid + 1;
+                } else if self.best.get(i) == Some(&mid) {
+                    hi = mid;
+                } else {
+                    // The value consumed different choices, start over here.
+                    break;
+                }
+            }
+            i += 1;
+        }
+    }
+}
+
+/// Configures and runs property checks.
+#[derive(Clone, Debug)]
+pub struct Check {
+    cases: u32,
+    seed: Option<u64>,
+    max_shrinks: u32,
+}
+
+impl Default for Check {
+    fn default() -> Self {
+        Self {
+            cases: 256,
+            seed: None,
+            max_shrinks: 4096,
+        }
+    }
+}
+
+impl Check {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Random cases to run, 256 by default.
+    pub fn cases(mut self, cases: u32) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    /// Derives the case seeds from `seed` instead of the thread generator.
+    /// Takes precedence over [`SEED_VAR`].
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Property calls shrinking may make, 4096 by default.
+    pub fn max_shrinks(mut self, max_shrinks: u32) -> Self {
+        self.max_shrinks = max_shrinks;
+        self
+    }
+
+    /// Checks `prop` and panics with the [`Failure`] if it fails.
+    pub fn run<T: Debug + 'static, O: Outcome>(&self, gen: &Gen<T>, prop: impl Fn(T) -> O) {
+        if let Err(failure) = self.check(gen, prop) {
+            panic!("{failure}");
+        }
+    }
+
+    /// Checks `prop` against random cases, or only the case in [`SEED_VAR`]
+    /// when it is set and no [`seed`](Self::seed) was given, and shrinks the
+    /// first failure. Panic messages of the property are not printed while
+    /// shrinking.
+    ///
+    /// # Panics
+    ///
+    /// If [`SEED_VAR`] is not a number, or filters reject more than half
+    /// of the cases.
+    pub fn check<T: 'static, O: Outcome>(
+        &self,
+        gen: &Gen<T>,
+        prop: impl Fn(T) -> O,
+    ) -> Result<(), Failure<T>> {
+        let seeds = self.case_seeds(std::env::var(SEED_VAR).ok());
+        self.check_seeds(gen, &prop, &seeds)
+    }
+
+    /// The case seeds, given the value of [`SEED_VAR`].
+    fn case_seeds(&self, var: Option<String>) -> Vec<u64> {
+        match (self.seed, var) {
+            (None, Some(seed)) => {
+                vec![parse_seed(&seed)
+                    .unwrap_or_else(|| panic!("{SEED_VAR} is not a number: {seed:?}"))]
+            }
+            (seed, _) => {
+                let mut base = SplitMix64::new(seed.unwrap_or_else(crate::random));
+                (0..self.cases).map(|_| base.next()).collect()
+            }
+        }
+    }
+
+    fn check_seeds<T: 'static, O: Outcome>(
+        &self,
+        gen: &Gen<T>,
+        prop: &impl Fn(T) -> O,
+        seeds: &[u64],
+    ) -> Result<(), Failure<T>> {
+        let mut rejected = 0;
+        for &seed in seeds {
+            let mut src = Source::random(seed);
+            let value = gen.generate(&mut src);
+            if src.rejected {
+                rejected += 1;
+                continue;
+            }
+            if let Err(message) = evaluate(prop, value) {
+                let mut shrinker = Shrinker {
+                    gen,
+                    prop,
+                    best: src.record.clone(),
+                    message,
+                    shrinks: 0,
+                    calls: 0,
+                    max_calls: self.max_shrinks,
+                };
+                quietly(|| shrinker.shrink());
+                return Err(Failure {
+                    seed,
+                    original: gen.generate(&mut Source::replay(&src.record)),
+                    minimal: gen.generate(&mut Source::replay(&shrinker.best)),
+                    shrinks: shrinker.shrinks,
+                    message: shrinker.message,
+                });
+            }
+        }
+        assert!(
+            rejected * 2 <= seeds.len(),
+            "filters rejected {rejected} of {} cases",
+            seeds.len()
+        );
+        Ok(())
+    }
+}
+
+/// Decimal or `0x` hexadecimal.
+fn parse_seed(s: &str) -> Option<u64> {
+    let s = s.trim();
+    match s.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
+
+/// Checks `prop` with the default [`Check`], panicking on failure.
+pub fn forall<T: Debug + 'static, O: Outcome>(gen: Gen<T>, prop: impl Fn(T) -> O) {
+    Check::new().run(&gen, prop)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn minimal<T: Debug + 'static, O: Outcome>(gen: Gen<T>, prop: impl Fn(T) -> O) -> T {
+        match Check::new().seed(1).check(&gen, prop) {
+            Err(failure) => failure.minimal,
+            Ok(()) => panic!("property passed"),
+        }
+    }
+
+    #[test]
+    fn passing_property() {
+        forall(any::<(u32, u32)>(), |(a, b)| {
+            a.wrapping_add(b) == b.wrapping_add(a)
+        });
+        forall(any::<String>(), |s| s.is_ascii());
+        forall(range(-5..=5_i8), |x| (-5..=5).contains(&x));
+        forall(range(0.5..2.0_f64), |x| (0.5..2.0).contains(&x));
+        // The width of the full range overflows to infinity.
+        forall(range(f64::MIN..f64::MAX), |x| x.is_finite() && x < f64::MAX);
+        forall(range(f32::MIN..f32::MAX), |x| x.is_finite() && x < f32::MAX);
+    }
+
+    #[test]
+    fn shrinks_numbers() {
+        assert_eq!(minimal(any::<u32>(), |x| x < 1000), 1000);
+        assert_eq!(minimal(range(-100..=100_i32), |x| x > -50), -50);
+        assert_eq!(minimal(any::<i64>(), |x| x.unsigned_abs() < 77), 77);
+        assert_eq!(minimal(any::<u128>(), |x| x < 1 << 70), 1 << 70);
+    }
+
+    #[test]
+    fn shrinks_collections() {
+        let v = minimal(vec(range(0..=50_u32), 0..=20), |v| {
+            v.iter().all(|&x| x <= 40)
+        });
+        assert_eq!(v, [41]);
+
+        let v = minimal(vec(any::<u8>(), 0..=20), |v| {
+            v.windows(2).all(|w| w[0] <= w[1])
+        });
+        assert_eq!(v, [1, 0]);
+
+        let s = minimal(string(0..=10), |s| !s.contains('z'));
+        assert_eq!(s, "z");
+
+        let v = minimal(vec(any::<u8>(), 3..=10), |v| v.len() < 5);
+        assert_eq!(v, [0; 5]);
+    }
+
+    #[test]
+    fn shrinks_through_combinators() {
+        let even = any::<u32>().filter(|x| x % 2 == 0).map(|x| x / 2);
+        assert_eq!(minimal(even, |x| x < 10), 10);
+
+        let sized = range(1..=5_usize).flat_map(|n| vec(just(n), n..=n));
+        assert_eq!(minimal(sized, |v| v.len() < 3), [3, 3, 3]);
+
+        // Odd values of the second alternative become odd values of the first.
+        let either = one_of(vec![range(0..10_i32), range(100..110)]);
+        assert_eq!(minimal(either, |x| x % 2 == 0), 1);
+
+        let mostly = weighted(vec![(9, just("common")), (1, just("rare"))]);
+        assert_eq!(minimal(mostly, |s| s == "common"), "rare");
+    }
+
+    #[test]
+    fn panics_are_failures() {
+        let failure = Check::new()
+            .seed(7)
+            .check(&any::<u16>(), |x| assert!(x < 300, "too big: {x}"))
+            .unwrap_err();
+        assert_eq!(failure.minimal, 300);
+        assert_eq!(failure.message, "too big: 300");
+        assert!(!QUIET.with(Cell::get));
+        assert!(failure.original >= 300);
+        assert!(failure
+            .to_string()
+            .contains(&format!("{SEED_VAR}={:#x}", failure.seed)));
+    }
+
+    #[test]
+    fn failing_seed_reproduces() {
+        let gen = vec(any::<u8>(), 0..=16);
+        let prop = |v: Vec<u8>| v.iter().filter(|&&b| b > 200).count() < 2;
+        let check = Check::new().seed(3);
+        let failure = check.check(&gen, prop).unwrap_err();
+        let again = check.check_seeds(&gen, &prop, &[failure.seed]).unwrap_err();
+        assert_eq!(again.original, failure.original);
+        assert_eq!(again.minimal, failure.minimal);
+        assert_eq!(failure.minimal, [201, 201]);
+
+        // An explicit seed wins over the variable.
+        let seeds = Check::new()
+            .cases(4)
+            .seed(3)
+            .case_seeds(Some("0x1f".into()));
+        assert_eq!(seeds, Check::new().cases(4).seed(3).case_seeds(None));
+        assert_eq!(seeds.len(), 4);
+        assert_eq!(Check::new().case_seeds(Some("0x1f".into())), [31]);
+
+        assert_eq!(parse_seed("0x1F"), Some(31));
+        assert_eq!(parse_seed(" 42\n"), Some(42));
+        assert_eq!(parse_seed("seed"), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "filters rejected")]
+    fn too_many_rejections() {
+        Check::new()
+            .seed(1)
+            .run(&any::<u8>().filter(|_| false), |_| true);
+    }
+}
@@ -0,0 +1,213 @@
+//! Conversions built from the per-width tables, so they work for every word
+//! width.
+//!
+//! The words a value consumes, in order:
+//!
+//! - `usize` / `isize`: one `u64` / `i64`, truncated on narrower targets, so
+//!   the words consumed do not depend on the platform. The value does: a
+//!   32-bit target keeps only the low half.
+//! - `char`: one value of `0..0x10f800` drawn like `next_range`, mapped past
+//!   the surrogates, so every scalar value is equally likely.
+//! - `NonZero*`: values of the primitive type until one is not zero.
+//! - `Wrapping<T>`: one `T`.
+//! - `[T; N]`: `N` values of `T`, index 0 first.
+//! - Tuples: one value per element, left to right.
+//! - `Option<T>`: one `bool`, then a `T` only when it is `true`.
+
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+};
+
+use crate::{FromWord, PrngCore, SampleRange};
+
+impl<W> FromWord<W> for usize
+where
+    u64: FromWord<W>,
+{
+    fn from_word<G: PrngCore<Word = W> + ?Sized>(rng: &mut G) -> Self {
+        u64::from_word(rng) as usize
+    }
+}
+
+impl<W> FromWord<W> for isize
+where
+    i64: FromWord<W>,
+{
+    fn from_word<G: PrngCore<Word = W> + ?Sized>(rng: &mut G) -> Self {
+        i64::from_word(rng) as isize
+    }
+}
+
+impl<W> FromWord<W> for char
+where
+    u64: FromWord<W>,
+{
+    fn from_word<G: PrngCore<Word = W> + ?Sized>(rng: &mut G) -> Self {
+        const SURROGATES: u32 = 0xe000 - 0xd800;
+        let n: u32 = (0..0x11_0000 - SURROGATES).sample(rng);
+        let n = if n < 0xd800 { n } else { n + SURROGATES };
+        char::from_u32(n).expect("surrogates are skipped")
+    }
+}
+
+macro_rules! impl_non_zero {
+    ($($nz:ty => $t:ty),*) => {$(
+        impl<W> FromWord<W> for $nz
+        where
+            $t: FromWord<W>,
+        {
+            fn from_word<G: PrngCore<Word = W> + ?Sized>(rng: &mut G) -> Self {
+                loop {
+                    if let Some(v) = <$nz>::new(<$t>::from_word(rng)) {
+                        return v;
+                    }
+                }
+            }
+        }
+    )*};
+}
+
+impl_non_zero!(
+    NonZeroU8 => u8, NonZeroU16 => u16, NonZeroU32 => u32, NonZeroU64 => u64,
+    NonZeroU128 => u128, NonZeroUsize => usize,
+    NonZeroI8 => i8, NonZeroI16 => i16, NonZeroI32 => i32, NonZeroI64 => i64,
+    NonZeroI128 => i128, NonZeroIsize => isize
+);
+
+impl<W, T: FromWord<W>> FromWord<W> for Wrapping<T> {
+    fn from_word<G: PrngCore<Word = W> + ?Sized>(rng: &mut G) -> Self {
+        Wrapping(T::from_word(rng))
+    }
+}
+
+impl<W, T: FromWord<W>, const N: usize> FromWord<W> for [T; N] {
+    fn from_word<G: PrngCore<Word = W> + ?Sized>(rng: &mut G) -> Self {
+        std::array::from_fn(|_| T::from_word(rng))
+    }
+}
+
+macro_rules! impl_tuple {
+    ($(($($t:ident),+)),*) => {$(
+        impl<W, $($t: FromWord<W>),+> FromWord<W> for ($($t,)+) {
+            fn from_word<G: PrngCore<Word = W> + ?Sized>(rng: &mut G) -> Self {
+                // Tuple expressions evaluate left to right.
+                ($($t::from_word(rng),)+)
+            }
+        }
+    )*};
+}
+
+impl_tuple!(
+    (A),
+    (A, B),
+    (A, B, C),
+    (A, B, C, D),
+    (A, B, C, D, E),
+    (A, B, C, D, E, F),
+    (A, B, C, D, E, F, H),
+    (A, B, C, D, E, F, H, I),
+    (A, B, C, D, E, F, H, I, J),
+    (A, B, C, D, E, F, H, I, J, K),
+    (A, B, C, D, E, F, H, I, J, K, L),
+    (A, B, C, D, E, F, H, I, J, K, L, M)
+);
+
+impl<W, T: FromWord<W>> FromWord<W> for Option<T>
+where
+    bool: FromWord<W>,
+{
+    fn from_word<G: PrngCore<Word = W> + ?Sized>(rng: &mut G) -> Self {
+        if bool::from_word(rng) {
+            Some(T::from_word(rng))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{mock::ScriptedRng, prelude::*};
+
+    #[test]
+    fn consumption_order() {
+        let mut rng = ScriptedRng::new(vec![1_u64 << 48, 2 << 48, 3 << 48]);
+        assert_eq!(rng.next::<[u8; 3]>(), [1, 2, 3]);
+
+        let mut rng = ScriptedRng::new(vec![1_u64 << 48, u64::MAX, 7]);
+        assert_eq!(rng.next::<(u8, bool, u64)>(), (1, true, 7));
+
+        // A u64 from a 32-bit generator is two words, low first.
+        let mut rng = ScriptedRng::new(vec![1_u32, 2, 3 << 24]);
+        assert_eq!(rng.next::<(u64, u8)>(), ((2 << 32) | 1, 3));
+
+        // None consumes only the bool.
+        let mut rng = ScriptedRng::new(vec![0_u64, u64::MAX, 5]);
+        assert_eq!(rng.next::<Option<u64>>(), None);
+        assert_eq!(rng.next::<Option<u64>>(), Some(5));
+        assert_eq!(rng.remaining(), 0);
+    }
+
+    #[test]
+    fn non_zero_retries() {
+        let mut rng = ScriptedRng::new(vec![0_u128, 0, 9]);
+        assert_eq!(rng.next::<NonZeroU128>().get(), 9);
+        assert_eq!(rng.remaining(), 0);
+    }
+
+    #[test]
+    fn char_covers_scalar_values() {
+        // The lowest and highest draws of 0..0x10f800. A 0 word would be
+        // redrawn by the unbiased range sampling.
+        let mut rng = ScriptedRng::new(vec![1_u64, u64::MAX]);
+        assert_eq!(rng.next::<char>(), '\0');
+        assert_eq!(rng.next::<char>(), char::MAX);
+
+        let mut rng = xorshiro(5_u32);
+        let mut above_surrogates = false;
+        for _ in 0..10_000 {
+            let c: char = rng.next();
+            above_surrogates |= c as u32 >= 0xe000;
+        }
+        assert!(above_surrogates);
+    }
+
+    #[test]
+    fn every_width() {
+        fn draw<G: PrngCore>(rng: &mut G) -> ([f32; 3], (u8, bool), Option<char>)
+        where
+            ([f32; 3], (u8, bool), Option<char>): FromWord<G::Word>,
+        {
+            rng.next()
+        }
+        draw(&mut xorshiro(1_u32));
+        draw(&mut xorshiro(1_u64));
+        draw(&mut xorshiro(1_u128));
+    }
+
+    #[test]
+    fn dump_value_types() {
+        let mut rng = split_mix(123456_u64);
+
+        let a = rng.next::<[u8; 4]>();
+        assert_eq!(a, [230, 246, 236, 33]);
+
+        let b = rng.next::<(i16, f32)>();
+        assert_eq!(b, (7256, 0.39085215));
+
+        let c = rng.next::<Option<u32>>();
+        assert_eq!(c, None);
+
+        let d = rng.next::<char>();
+        assert_eq!(d, '\u{f4711}');
+
+        let e = rng.next::<NonZeroU16>().get();
+        assert_eq!(e, 26118);
+
+        // The low half of the same word on 32-bit targets.
+        let f = rng.next::<Wrapping<usize>>().0;
+        assert_eq!(f, 13754107039689013136_u64 as usize);
+    }
+}
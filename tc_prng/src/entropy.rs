@@ -0,0 +1,147 @@
+//! Seed material from the operating system.
+//!
+//! The OS source is `/dev/urandom`, read through `std::fs`, which the kernel
+//! feeds from the same pool as `getrandom(2)`. That is [`os_entropy`].
+//!
+//! [`entropy`] adds a fallback for when the OS source is unavailable (e.g.
+//! inside a sandbox without `/dev`): the bytes are derived from whatever the
+//! process can observe instead, the wall clock, the process id, stack and
+//! heap addresses and std's own randomly keyed `RandomState`. That is fine
+//! for seeding simulations and games, but guessable, so the cryptographic
+//! generators only ever use [`os_entropy`].
+
+use std::{
+    collections::hash_map::RandomState,
+    fmt,
+    fs::File,
+    hash::{BuildHasher, Hash, Hasher},
+    io::{self, Read},
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, SystemTimeError, UNIX_EPOCH},
+};
+
+use crate::split_mix::SplitMix64;
+
+/// Why no seed material could be produced.
+#[derive(Debug)]
+pub enum EntropyError {
+    /// `/dev/urandom` could not be read.
+    Os(io::Error),
+    /// The OS source failed and the fallback could not read the system clock.
+    Exhausted {
+        os: io::Error,
+        clock: SystemTimeError,
+    },
+}
+
+impl fmt::Display for EntropyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntropyError::Os(e) => write!(f, "reading OS entropy failed: {e}"),
+            EntropyError::Exhausted { os, clock } => write!(
+                f,
+                "reading OS entropy failed ({os}) and the fallback clock is unusable ({clock})"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EntropyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            EntropyError::Os(e) => Some(e),
+            EntropyError::Exhausted { os, .. } => Some(os),
+        }
+    }
+}
+
+/// Fills `dst` from the OS, without any fallback. Use this for keys.
+pub fn os_entropy(dst: &mut [u8]) -> Result<(), EntropyError> {
+    urandom(dst).map_err(EntropyError::Os)
+}
+
+/// Fills `dst` from the OS, falling back to process-observable entropy.
+///
+/// Only for seeding non-cryptographic generators: the fallback can be
+/// guessed by anyone who knows roughly when and where the process ran.
+pub fn entropy(dst: &mut [u8]) -> Result<(), EntropyError> {
+    urandom(dst).or_else(|os| fallback(dst).map_err(|clock| EntropyError::Exhausted { os, clock }))
+}
+
+fn urandom(dst: &mut [u8]) -> io::Result<()> {
+    File::open("/dev/urandom")?.read_exact(dst)
+}
+
+/// Mixes time, pid, addresses and a call counter into a `SplitMix64` seed.
+pub(crate) fn fallback(dst: &mut [u8]) -> Result<(), SystemTimeError> {
+    static CALLS: AtomicU64 = AtomicU64::new(0);
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+    let stack = 0u8;
+    let heap = Box::new(0u8);
+
+    // std seeds RandomState from the OS itself when it can.
+    let mut h = RandomState::new().build_hasher();
+    h.write_u128(now.as_nanos());
+    h.write_u32(std::process::id());
+    h.write_usize(&stack as *const u8 as usize);
+    h.write_usize(&*heap as *const u8 as usize);
+    h.write_u64(CALLS.fetch_add(1, Ordering::Relaxed));
+    std::thread::current().id().hash(&mut h);
+
+    let mut rng = SplitMix64::new(h.finish());
+    for c in dst.chunks_mut(8) {
+        let v: u64 = rng.next();
+        c.copy_from_slice(&v.to_le_bytes()[..c.len()]);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn fills_distinct_buffers() {
+        let mut a = [0u8; 32];
+        let mut b = [0u8; 32];
+        entropy(&mut a).unwrap();
+        entropy(&mut b).unwrap();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn os_fills_every_byte() {
+        // Larger than a single read returns for some kernels.
+        let mut a = vec![0u8; 1 << 16];
+        os_entropy(&mut a).unwrap();
+        assert!(a.chunks(64).all(|c| c.iter().any(|b| *b != 0)));
+    }
+
+    #[test]
+    fn fallback_is_distinct() {
+        let mut a = [0u8; 13];
+        let mut b = [0u8; 13];
+        fallback(&mut a).unwrap();
+        fallback(&mut b).unwrap();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn generators_from_entropy() {
+        let mut a = Xorshiro64::from_entropy().unwrap();
+        let mut b = Xorshiro64::from_entropy().unwrap();
+        assert_ne!(a.next::<u64>(), b.next::<u64>());
+
+        let mut a = SplitMix64::from_entropy().unwrap();
+        let mut b = SplitMix64::from_entropy().unwrap();
+        assert_ne!(a.next::<u64>(), b.next::<u64>());
+    }
+
+    #[test]
+    fn error_reports_cause() {
+        let e = EntropyError::Os(io::Error::new(io::ErrorKind::NotFound, "no /dev"));
+        assert_eq!(e.to_string(), "reading OS entropy failed: no /dev");
+    }
+}
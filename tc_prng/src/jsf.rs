@@ -0,0 +1,193 @@
+//! Bob Jenkins' small fast generators.
+//!
+//! Four words of chaotic state updated with one subtraction, a xor, two
+//! additions and two or three rotations. There is no guaranteed period,
+//! but Jenkins' seeding (`a` fixed to `0xf1ea5eed`, the seed in the other
+//! three words, 20 outputs discarded) was searched for short cycles and the
+//! expected period is around 2^126 for [`Jsf32`] and 2^255 for [`Jsf64`].
+//!
+//! `new(seed)` is Jenkins' `raninit`; the raw words match his reference code
+//! and PractRand's `jsf32` / `jsf64` for the same seed.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tc_prng::jsf::Jsf32;
+//!
+//! let mut rng = Jsf32::new(42);
+//! assert_eq!(rng.next::<u32>(), 1230419127);
+//! ```
+
+use crate::{
+    state::{Algorithm, Codec, Persist, Reader, StateError},
+    Prng, PrngCore, Seedable,
+};
+
+/// 32-bit JSF, 128 bits of state.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Jsf32 {
+    a: u32,
+    b: u32,
+    c: u32,
+    d: u32,
+}
+
+/// 64-bit JSF, 256 bits of state.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Jsf64 {
+    a: u64,
+    b: u64,
+    c: u64,
+    d: u64,
+}
+
+impl PrngCore for Jsf32 {
+    type Word = u32;
+
+    fn calc(&mut self) -> u32 {
+        let e = self.a.wrapping_sub(self.b.rotate_left(27));
+        self.a = self.b ^ self.c.rotate_left(17);
+        self.b = self.c.wrapping_add(self.d);
+        self.c = self.d.wrapping_add(e);
+        self.d = e.wrapping_add(self.a);
+        self.d
+    }
+}
+
+impl PrngCore for Jsf64 {
+    type Word = u64;
+
+    fn calc(&mut self) -> u64 {
+        let e = self.a.wrapping_sub(self.b.rotate_left(7));
+        self.a = self.b ^ self.c.rotate_left(13);
+        self.b = self.c.wrapping_add(self.d.rotate_left(37));
+        self.c = self.d.wrapping_add(e);
+        self.d = e.wrapping_add(self.a);
+        self.d
+    }
+}
+
+macro_rules! impl_jsf {
+    ($($ty:ident: $word:ty, $bits:literal, $read:ident;)*) => {$(
+        impl $ty {
+            pub fn new(seed: $word) -> Self {
+                let mut rng = Self {
+                    a: 0xf1ea5eed,
+                    b: seed,
+                    c: seed,
+                    d: seed,
+                };
+                for _ in 0..20 {
+                    rng.calc();
+                }
+                rng
+            }
+
+            #[allow(clippy::should_implement_trait)]
+            pub fn next<V>(&mut self) -> V
+            where
+                Self: Prng<V>,
+            {
+                Prng::next_val(self)
+            }
+        }
+
+        impl Default for $ty {
+            fn default() -> Self {
+                Self::new(0)
+            }
+        }
+
+        impl Seedable for $ty {
+            type Seed = [u8; $bits / 8];
+
+            fn from_seed(seed: Self::Seed) -> Self {
+                Self::new(<$word>::from_le_bytes(seed))
+            }
+        }
+
+        impl Persist for $ty {
+            /// `[a, b, c, d]`.
+            type State = [$word; 4];
+
+            const ALGORITHM: Algorithm = Algorithm::Jsf;
+            const VARIANT: u8 = $bits;
+            const STATE_LEN: usize = 4 * $bits / 8;
+
+            fn state(&self) -> Self::State {
+                [self.a, self.b, self.c, self.d]
+            }
+
+            fn from_state([a, b, c, d]: Self::State) -> Self {
+                Self { a, b, c, d }
+            }
+        }
+
+        impl Codec for $ty {
+            fn encode(&self, out: &mut Vec<u8>) {
+                for w in self.state() {
+                    out.extend_from_slice(&w.to_le_bytes());
+                }
+            }
+
+            fn decode(bytes: &[u8]) -> Result<Self, StateError> {
+                let mut r = Reader(bytes);
+                Ok(Self::from_state(std::array::from_fn(|_| r.$read())))
+            }
+        }
+    )*};
+}
+
+impl_jsf! {
+    Jsf32: u32, 32, u32;
+    Jsf64: u64, 64, u64;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Reference values from Jenkins' `ranval` / `raninit`.
+
+    #[test]
+    fn reference_vectors() {
+        let mut rng = Jsf32::new(42);
+        assert_eq!(
+            [rng.calc(), rng.calc(), rng.calc()],
+            [1230419127, 4080097750, 2014035305]
+        );
+        let mut rng = Jsf32::new(7);
+        let v = (0..1000).map(|_| rng.calc()).last();
+        assert_eq!(v, Some(397390764));
+
+        let mut rng = Jsf64::new(42);
+        assert_eq!(
+            [rng.calc(), rng.calc(), rng.calc()],
+            [
+                11921485425870369842,
+                6950967119895308506,
+                3738120138616583258
+            ]
+        );
+        let mut rng = Jsf64::new(7);
+        let v = (0..1000).map(|_| rng.calc()).last();
+        assert_eq!(v, Some(7902305726627885522));
+    }
+
+    #[test]
+    fn dump_value_types() {
+        let mut rng = Jsf64::new(123456);
+
+        let a: u8 = rng.next();
+        assert_eq!(a, 73);
+
+        let b: u128 = rng.next();
+        assert_eq!(b, 262922263077470365583182016584468865649);
+
+        let c: bool = rng.next();
+        assert!(!c);
+
+        let d: f32 = rng.next();
+        assert_eq!(d, 0.16099598);
+    }
+}
@@ -0,0 +1,196 @@
+//! Multi-lane generators for batch output.
+//!
+//! A scalar generator has to finish one step before starting the next. The
+//! generators here run `N` independent 64-bit states side by side in a
+//! `[u64; N]`, so each step is the same arithmetic applied to every lane
+//! and the compiler can keep the lanes in vector registers. Use them to fill
+//! large buffers; for single values the scalar generators are just as fast.
+//!
+//! - [`SplitMixLanes`] interleaves the scalar [`SplitMix64`] stream: its
+//!   blocks, read in order, are exactly the words `split_mix(seed)` produces.
+//! - [`XorshiroLanes`] runs `N` separate streams. Lane 0 is the scalar
+//!   `xorshiro(seed)` stream; lane `i` starts from the `i`-th nonzero word
+//!   of `split_mix(seed)`, just as `xorshiro` seeds its single lane.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tc_prng::{lanes::SplitMix64x4, prelude::*};
+//!
+//! let mut lanes = SplitMix64x4::new(7);
+//! let mut scalar = split_mix(7_u64);
+//! let block = lanes.next_block();
+//! assert_eq!(block[0], scalar.next::<u64>());
+//! assert_eq!(block[1], scalar.next::<u64>());
+//!
+//! let mut noise = vec![0.0_f32; 4096];
+//! lanes.fill_f32(&mut noise);
+//! ```
+
+use crate::{split_mix::SplitMix64, xorshiro::Xorshiro64};
+
+pub type SplitMix64x4 = SplitMixLanes<4>;
+pub type SplitMix64x8 = SplitMixLanes<8>;
+pub type Xorshiro64x4 = XorshiroLanes<4>;
+pub type Xorshiro64x8 = XorshiroLanes<8>;
+
+/// `N` interleaved lanes of [`SplitMix64`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SplitMixLanes<const N: usize> {
+    state: [u64; N],
+}
+
+impl<const N: usize> SplitMixLanes<N> {
+    const STEP: u64 = SplitMix64::ADD.wrapping_mul(N as u64);
+
+    pub fn new(seed: u64) -> Self {
+        // Lane `i` sits `i` steps ahead of the scalar generator and every
+        // block moves all lanes `N` steps.
+        Self {
+            state: std::array::from_fn(|i| {
+                seed.wrapping_add(SplitMix64::ADD.wrapping_mul(i as u64))
+            }),
+        }
+    }
+
+    #[inline]
+    pub fn next_block(&mut self) -> [u64; N] {
+        let mut out = [0; N];
+        for (o, s) in out.iter_mut().zip(self.state.iter_mut()) {
+            *o = SplitMix64::mix(s.wrapping_add(SplitMix64::ADD));
+            *s = s.wrapping_add(Self::STEP);
+        }
+        out
+    }
+
+    /// Fills `dst` block by block. Words of a trailing partial block that
+    /// don't fit are discarded.
+    pub fn fill(&mut self, dst: &mut [u64]) {
+        fill(dst, || self.next_block());
+    }
+
+    /// Fills `dst` with floats in `[0, 1]`, converted like `next::<f32>()`
+    /// on a 64-bit generator.
+    pub fn fill_f32(&mut self, dst: &mut [f32]) {
+        fill_f32(dst, || self.next_block());
+    }
+}
+
+/// `N` independent lanes of [`Xorshiro64`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct XorshiroLanes<const N: usize> {
+    state: [u64; N],
+}
+
+impl<const N: usize> XorshiroLanes<N> {
+    pub fn new(seed: u64) -> Self {
+        let mut seeder = SplitMix64::new(seed);
+        Self {
+            state: std::array::from_fn(|_| Xorshiro64::seed_from(&mut seeder)),
+        }
+    }
+
+    #[inline]
+    pub fn next_block(&mut self) -> [u64; N] {
+        let mut out = [0; N];
+        for (o, s) in out.iter_mut().zip(self.state.iter_mut()) {
+            *o = s.wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+            *s = Xorshiro64::xorshiro(*s);
+        }
+        out
+    }
+
+    /// Fills `dst` block by block. Words of a trailing partial block that
+    /// don't fit are discarded.
+    pub fn fill(&mut self, dst: &mut [u64]) {
+        fill(dst, || self.next_block());
+    }
+
+    /// Fills `dst` with floats in `[0, 1]`, converted like `next::<f32>()`
+    /// on a 64-bit generator.
+    pub fn fill_f32(&mut self, dst: &mut [f32]) {
+        fill_f32(dst, || self.next_block());
+    }
+}
+
+#[inline]
+fn fill<const N: usize>(dst: &mut [u64], mut block: impl FnMut() -> [u64; N]) {
+    let mut chunks = dst.chunks_exact_mut(N);
+    for c in &mut chunks {
+        c.copy_from_slice(&block());
+    }
+    let rest = chunks.into_remainder();
+    if !rest.is_empty() {
+        rest.copy_from_slice(&block()[..rest.len()]);
+    }
+}
+
+#[inline]
+fn fill_f32<const N: usize>(dst: &mut [f32], mut block: impl FnMut() -> [u64; N]) {
+    // Same bits and scale as `FromWord<u64> for f32`.
+    const MASK: u64 = (1 << 23) - 1;
+    const D: f32 = MASK as f32;
+    let convert = |c: &mut [f32], b: [u64; N]| {
+        for (f, w) in c.iter_mut().zip(b) {
+            *f = ((w >> 42) & MASK) as f32 / D;
+        }
+    };
+
+    let mut chunks = dst.chunks_exact_mut(N);
+    for c in &mut chunks {
+        convert(c, block());
+    }
+    let rest = chunks.into_remainder();
+    if !rest.is_empty() {
+        convert(rest, block());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn split_mix_lanes_match_scalar_stream() {
+        let mut lanes = SplitMix64x8::new(123);
+        let mut scalar = split_mix(123_u64);
+        for _ in 0..10 {
+            for w in lanes.next_block() {
+                assert_eq!(w, scalar.next::<u64>());
+            }
+        }
+
+        let mut words = vec![0; 37];
+        SplitMix64x4::new(5).fill(&mut words);
+        let mut scalar = split_mix(5_u64);
+        assert!(words.iter().all(|w| *w == scalar.next::<u64>()));
+    }
+
+    #[test]
+    fn xorshiro_lanes_are_seeded_streams() {
+        let mut lanes = Xorshiro64x4::new(9);
+        let mut first = xorshiro(9_u64);
+        let blocks: Vec<_> = (0..5).map(|_| lanes.next_block()).collect();
+        for b in &blocks {
+            assert_eq!(b[0], first.next::<u64>());
+        }
+        assert_ne!(blocks[0][0], blocks[0][1]);
+    }
+
+    #[test]
+    fn fill_f32_matches_scalar_conversion() {
+        let mut floats = vec![0.0; 21];
+        SplitMix64x8::new(1).fill_f32(&mut floats);
+        let mut scalar = split_mix(1_u64);
+        for f in floats {
+            assert_eq!(f, scalar.next::<f32>());
+        }
+
+        let mut floats = [0.0; 8];
+        Xorshiro64x4::new(2).fill_f32(&mut floats);
+        let mut first = xorshiro(2_u64);
+        assert_eq!(floats[0], first.next::<f32>());
+        assert_eq!(floats[4], first.next::<f32>());
+    }
+}
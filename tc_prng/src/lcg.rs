@@ -0,0 +1,707 @@
+//! Legacy generators from C libraries, bit-exact with their originals.
+//!
+//! - [`MinStdRand0`] and [`MinStdRand`]: Park and Miller's "minimal
+//!   standard" `x = a * x mod (2^31 - 1)` with `a = 16807` and `a = 48271`,
+//!   as C++'s `std::minstd_rand0` / `std::minstd_rand`.
+//! - [`Drand48`]: the POSIX 48-bit LCG behind `drand48`, `lrand48` and
+//!   `mrand48`; [`erand48`], [`nrand48`] and [`jrand48`] run it on caller
+//!   owned state.
+//! - [`GlibcRand`]: glibc's `rand()` / `random()`, an additive feedback
+//!   generator over a 34 word table seeded by an LCG.
+//! - [`AnsiCRand`]: the portable `rand()` given as an example in the C
+//!   standard, `x = x * 1103515245 + 12345`, returning bits 16 to 30.
+//!
+//! The true LCGs can [`advance`](Drand48::advance) `n` steps in O(log n).
+//!
+//! The named methods (`next_raw`, `rand`, `drand48`, ...) return the
+//! original outputs. The typed conversions of `next::<T>()` need full 32-bit
+//! words, so [`calc`](PrngCore::calc) builds them from as many outputs as it
+//! takes: the top 16 bits of two outputs for minstd and glibc, high half
+//! first, and the 15 bits of three for the ANSI example, keeping the top 32
+//! of the 45. `drand48` already has 32 bits per step. `advance` counts steps
+//! of the original generator, not words.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tc_prng::lcg::{Drand48, GlibcRand};
+//!
+//! // srand48(42); drand48();
+//! let mut rng = Drand48::new(42);
+//! assert_eq!(rng.drand48(), 0.7445250000610066);
+//!
+//! // srand(1); rand();
+//! assert_eq!(GlibcRand::new(1).rand(), 1804289383);
+//! ```
+
+use crate::{
+    state::{Algorithm, Codec, Persist, Reader, StateError},
+    Prng, PrngCore, Seedable,
+};
+
+/// Applies `n` steps of `x = a * x + c mod m` in O(log n).
+fn jump(a: u64, c: u64, m: u64, x: u64, mut n: u128) -> u64 {
+    let m = m as u128;
+    let (mut a, mut c) = (a as u128, c as u128);
+    let (mut acc_a, mut acc_c) = (1, 0);
+    while n != 0 {
+        if n & 1 == 1 {
+            acc_a = acc_a * a % m;
+            acc_c = (acc_c * a + c) % m;
+        }
+        c = (a + 1) * c % m;
+        a = a * a % m;
+        n >>= 1;
+    }
+    ((acc_a * x as u128 + acc_c) % m) as u64
+}
+
+/// Park-Miller minimal standard generator with multiplier `A`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MinStd<const A: u32> {
+    x: u32,
+}
+
+pub type MinStdRand0 = MinStd<16807>;
+pub type MinStdRand = MinStd<48271>;
+
+impl<const A: u32> Default for MinStd<A> {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl<const A: u32> Seedable for MinStd<A> {
+    type Seed = [u8; 4];
+
+    /// Reduces the seed to a nonzero residue mod `2^31 - 1`, like
+    /// [`new`](Self::new).
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(u32::from_le_bytes(seed))
+    }
+}
+
+impl<const A: u32> MinStd<A> {
+    const M: u32 = 0x7fff_ffff;
+
+    /// Seeds like the C++ constructor: `seed mod (2^31 - 1)`, with 0
+    /// replaced by 1.
+    pub fn new(seed: u32) -> Self {
+        match seed % Self::M {
+            0 => Self { x: 1 },
+            x => Self { x },
+        }
+    }
+
+    /// Moves `n` steps forward in O(log n).
+    pub fn advance(&mut self, n: u128) {
+        self.x = jump(A as u64, 0, Self::M as u64, self.x as u64, n) as u32;
+    }
+
+    /// The next output of the C++ generator, in `1..2^31 - 1`.
+    pub fn next_raw(&mut self) -> u32 {
+        self.x = (self.x as u64 * A as u64 % Self::M as u64) as u32;
+        self.x
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<V>(&mut self) -> V
+    where
+        Self: Prng<V>,
+    {
+        Prng::next_val(self)
+    }
+}
+
+impl<const A: u32> PrngCore for MinStd<A> {
+    type Word = u32;
+
+    /// The top 16 bits of two outputs, high half first.
+    fn calc(&mut self) -> u32 {
+        let hi = self.next_raw() >> 15;
+        let lo = self.next_raw() >> 15;
+        hi << 16 | lo
+    }
+}
+
+/// The `drand48` family's 48-bit LCG.
+///
+/// `Default` matches glibc before any seeding call, whose state is zero.
+/// System V and the BSDs instead start from `0x1234ABCD330E`, available as
+/// `Drand48::from_state(Drand48::SYSV_INITIAL)`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Drand48 {
+    x: u64,
+}
+
+impl Seedable for Drand48 {
+    type Seed = [u8; 6];
+
+    /// Every 48-bit state is valid; the bytes are the state, like
+    /// [`from_xsubi`](Self::from_xsubi).
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut x = [0; 8];
+        x[..6].copy_from_slice(&seed);
+        Self::from_state(u64::from_le_bytes(x))
+    }
+}
+
+impl Drand48 {
+    const A: u64 = 0x5_DEEC_E66D;
+    const C: u64 = 0xB;
+    const MASK: u64 = (1 << 48) - 1;
+
+    pub const SYSV_INITIAL: u64 = 0x1234_ABCD_330E;
+
+    /// Seeds like `srand48`: the low 32 bits of `seed` become the high 32
+    /// bits of the state.
+    pub fn new(seed: i64) -> Self {
+        Self {
+            x: ((seed as u64 & 0xffff_ffff) << 16) | 0x330E,
+        }
+    }
+
+    /// Seeds like `seed48`, from three 16-bit words, least significant
+    /// first.
+    pub fn from_xsubi(xsubi: [u16; 3]) -> Self {
+        Self {
+            x: xsubi[0] as u64 | (xsubi[1] as u64) << 16 | (xsubi[2] as u64) << 32,
+        }
+    }
+
+    /// The state as the three 16-bit words used by `erand48` and friends.
+    pub fn xsubi(&self) -> [u16; 3] {
+        [self.x as u16, (self.x >> 16) as u16, (self.x >> 32) as u16]
+    }
+
+    /// Moves `n` steps forward in O(log n).
+    pub fn advance(&mut self, n: u128) {
+        self.x = jump(Self::A, Self::C, 1 << 48, self.x, n);
+    }
+
+    /// `drand48()`: a double in `[0, 1)` holding all 48 bits of the state.
+    pub fn drand48(&mut self) -> f64 {
+        self.step() as f64 / (1_u64 << 48) as f64
+    }
+
+    /// `lrand48()`: the top 31 bits, in `0..2^31`.
+    pub fn lrand48(&mut self) -> i64 {
+        (self.step() >> 17) as i64
+    }
+
+    /// `mrand48()`: the top 32 bits as a signed value.
+    pub fn mrand48(&mut self) -> i64 {
+        (self.step() >> 16) as u32 as i32 as i64
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<V>(&mut self) -> V
+    where
+        Self: Prng<V>,
+    {
+        Prng::next_val(self)
+    }
+
+    fn step(&mut self) -> u64 {
+        self.x = Self::A.wrapping_mul(self.x).wrapping_add(Self::C) & Self::MASK;
+        self.x
+    }
+}
+
+impl PrngCore for Drand48 {
+    type Word = u32;
+
+    /// The top 32 bits of the next state, as `mrand48` without the sign.
+    fn calc(&mut self) -> u32 {
+        (self.step() >> 16) as u32
+    }
+}
+
+/// `erand48(xsubi)`: `drand48` on caller-owned state.
+pub fn erand48(xsubi: &mut [u16; 3]) -> f64 {
+    with_xsubi(xsubi, Drand48::drand48)
+}
+
+/// `nrand48(xsubi)`: `lrand48` on caller-owned state.
+pub fn nrand48(xsubi: &mut [u16; 3]) -> i64 {
+    with_xsubi(xsubi, Drand48::lrand48)
+}
+
+/// `jrand48(xsubi)`: `mrand48` on caller-owned state.
+pub fn jrand48(xsubi: &mut [u16; 3]) -> i64 {
+    with_xsubi(xsubi, Drand48::mrand48)
+}
+
+fn with_xsubi<T>(xsubi: &mut [u16; 3], f: impl FnOnce(&mut Drand48) -> T) -> T {
+    let mut rng = Drand48::from_xsubi(*xsubi);
+    let v = f(&mut rng);
+    *xsubi = rng.xsubi();
+    v
+}
+
+/// glibc's `rand()`, the default `TYPE_3` additive feedback generator.
+///
+/// Not an LCG: each output is `r[i] = r[i - 3] + r[i - 31]`, so there is no
+/// cheap skip-ahead.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GlibcRand {
+    r: [u32; 34],
+    idx: usize,
+}
+
+impl Default for GlibcRand {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl Seedable for GlibcRand {
+    type Seed = [u8; 4];
+
+    /// Seeds like `srand`, see [`new`](Self::new).
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(u32::from_le_bytes(seed))
+    }
+}
+
+impl GlibcRand {
+    /// Seeds like `srand`; 0 is treated as 1.
+    pub fn new(seed: u32) -> Self {
+        let mut r = [0_u32; 34];
+        r[0] = if seed == 0 { 1 } else { seed };
+        for i in 1..31 {
+            // 16807 * r mod (2^31 - 1) on the signed value, via Schrage's
+            // method as in glibc.
+            let prev = r[i - 1] as i32 as i64;
+            let mut v = 16807 * (prev % 127773) - 2836 * (prev / 127773);
+            if v < 0 {
+                v += 2147483647;
+            }
+            r[i] = v as u32;
+        }
+        for i in 31..34 {
+            r[i] = r[i - 31];
+        }
+        let mut rng = Self { r, idx: 0 };
+        for _ in 0..310 {
+            rng.step();
+        }
+        rng
+    }
+
+    /// The next `rand()` value, in `0..2^31`.
+    pub fn rand(&mut self) -> i32 {
+        (self.step() >> 1) as i32
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<V>(&mut self) -> V
+    where
+        Self: Prng<V>,
+    {
+        Prng::next_val(self)
+    }
+
+    fn step(&mut self) -> u32 {
+        // `r` is a ring of the last 34 values; `idx` is the oldest.
+        let v = self.r[(self.idx + 31) % 34].wrapping_add(self.r[(self.idx + 3) % 34]);
+        self.r[self.idx] = v;
+        self.idx = (self.idx + 1) % 34;
+        v
+    }
+}
+
+impl PrngCore for GlibcRand {
+    type Word = u32;
+
+    /// The top 16 bits of two `rand()` values, high half first.
+    fn calc(&mut self) -> u32 {
+        let hi = self.rand() as u32 >> 15;
+        let lo = self.rand() as u32 >> 15;
+        hi << 16 | lo
+    }
+}
+
+/// The example `rand()` from the C standard, with `RAND_MAX` 32767.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AnsiCRand {
+    x: u32,
+}
+
+impl Default for AnsiCRand {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl Seedable for AnsiCRand {
+    type Seed = [u8; 4];
+
+    /// Every 32-bit state is valid; seeds like `srand`.
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(u32::from_le_bytes(seed))
+    }
+}
+
+impl AnsiCRand {
+    const A: u32 = 1103515245;
+    const C: u32 = 12345;
+
+    /// Seeds like `srand`.
+    pub fn new(seed: u32) -> Self {
+        Self { x: seed }
+    }
+
+    /// Moves `n` steps forward in O(log n).
+    pub fn advance(&mut self, n: u128) {
+        self.x = jump(Self::A as u64, Self::C as u64, 1 << 32, self.x as u64, n) as u32;
+    }
+
+    /// The next `rand()` value, in `0..32768`.
+    pub fn rand(&mut self) -> i32 {
+        self.x = self.x.wrapping_mul(Self::A).wrapping_add(Self::C);
+        ((self.x >> 16) & 0x7fff) as i32
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<V>(&mut self) -> V
+    where
+        Self: Prng<V>,
+    {
+        Prng::next_val(self)
+    }
+}
+
+impl PrngCore for AnsiCRand {
+    type Word = u32;
+
+    /// Three `rand()` values, 15 bits each, first value highest; the lowest
+    /// 13 bits of the last one are dropped.
+    fn calc(&mut self) -> u32 {
+        let a = self.rand() as u32;
+        let b = self.rand() as u32;
+        let c = self.rand() as u32;
+        a << 17 | b << 2 | c >> 13
+    }
+}
+
+macro_rules! impl_persist_min_std {
+    ($($ty:ty => $alg:ident),*) => {$(
+        impl Persist for $ty {
+            type State = u32;
+
+            const ALGORITHM: Algorithm = Algorithm::$alg;
+            const VARIANT: u8 = 32;
+            const STATE_LEN: usize = 4;
+
+            fn state(&self) -> u32 {
+                self.x
+            }
+
+            fn from_state(state: u32) -> Self {
+                Self::new(state)
+            }
+        }
+
+        impl Codec for $ty {
+            fn encode(&self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.x.to_le_bytes());
+            }
+
+            fn decode(bytes: &[u8]) -> Result<Self, StateError> {
+                Ok(Self::from_state(Reader(bytes).u32()))
+            }
+        }
+    )*};
+}
+
+impl_persist_min_std!(MinStdRand0 => MinStdRand0, MinStdRand => MinStdRand);
+
+impl Persist for Drand48 {
+    type State = u64;
+
+    const ALGORITHM: Algorithm = Algorithm::Drand48;
+    const VARIANT: u8 = 48;
+    const STATE_LEN: usize = 8;
+
+    fn state(&self) -> u64 {
+        self.x
+    }
+
+    fn from_state(state: u64) -> Self {
+        Self {
+            x: state & Self::MASK,
+        }
+    }
+}
+
+impl Codec for Drand48 {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.x.to_le_bytes());
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, StateError> {
+        Ok(Self::from_state(Reader(bytes).u64()))
+    }
+}
+
+impl Persist for GlibcRand {
+    type State = ([u32; 34], usize);
+
+    const ALGORITHM: Algorithm = Algorithm::GlibcRand;
+    const VARIANT: u8 = 32;
+    const STATE_LEN: usize = 34 * 4 + 1;
+
+    fn state(&self) -> Self::State {
+        (self.r, self.idx)
+    }
+
+    fn from_state((r, idx): Self::State) -> Self {
+        Self { r, idx: idx % 34 }
+    }
+}
+
+impl Codec for GlibcRand {
+    fn encode(&self, out: &mut Vec<u8>) {
+        for w in self.r {
+            out.extend_from_slice(&w.to_le_bytes());
+        }
+        out.push(self.idx as u8);
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, StateError> {
+        let mut r = Reader(bytes);
+        let words = std::array::from_fn(|_| r.u32());
+        Ok(Self::from_state((words, bytes[34 * 4] as usize)))
+    }
+}
+
+impl Persist for AnsiCRand {
+    type State = u32;
+
+    const ALGORITHM: Algorithm = Algorithm::AnsiCRand;
+    const VARIANT: u8 = 32;
+    const STATE_LEN: usize = 4;
+
+    fn state(&self) -> u32 {
+        self.x
+    }
+
+    fn from_state(state: u32) -> Self {
+        Self::new(state)
+    }
+}
+
+impl Codec for AnsiCRand {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.x.to_le_bytes());
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, StateError> {
+        Ok(Self::from_state(Reader(bytes).u32()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Reference values from glibc and libstdc++.
+
+    #[test]
+    fn minstd() {
+        let mut rng = MinStdRand0::default();
+        let v = (0..10000).map(|_| rng.next_raw()).last();
+        assert_eq!(v, Some(1043618065));
+
+        let mut rng = MinStdRand::default();
+        let v = (0..10000).map(|_| rng.next_raw()).last();
+        assert_eq!(v, Some(399268537));
+
+        assert_eq!(MinStdRand::new(0).next_raw(), 48271);
+        assert_eq!(MinStdRand::new(0x7fff_ffff).next_raw(), 48271);
+    }
+
+    #[test]
+    fn drand48_family() {
+        let mut rng = Drand48::default();
+        assert_eq!(rng.drand48(), 3.907985046680551e-14);
+        assert_eq!(rng.drand48(), 0.0009853946746503084);
+
+        let mut rng = Drand48::new(42);
+        assert_eq!([rng.lrand48(), rng.lrand48()], [1598855263, 735945821]);
+        let mut rng = Drand48::new(42);
+        assert_eq!([rng.mrand48(), rng.mrand48()], [-1097256770, 1471891643]);
+        assert_eq!(Drand48::new(42).drand48(), 0.7445250000610066);
+        assert_eq!(Drand48::new(-1).lrand48(), 644300343);
+
+        let mut rng = Drand48::new(7);
+        let v = (0..1000).map(|_| rng.lrand48()).last();
+        assert_eq!(v, Some(907751414));
+    }
+
+    #[test]
+    fn xsubi_functions() {
+        let mut x = [0x1234, 0x5678, 0x9abc];
+        assert_eq!(erand48(&mut x), 0.286599243634722);
+        assert_eq!(x, [0x782f, 0x916a, 0x495e]);
+        assert_eq!(nrand48(&mut x), 2006585297);
+        assert_eq!(jrand48(&mut x), -1996062933);
+    }
+
+    #[test]
+    fn glibc_rand() {
+        let mut rng = GlibcRand::new(1);
+        assert_eq!(
+            [rng.rand(), rng.rand(), rng.rand()],
+            [1804289383, 846930886, 1681692777]
+        );
+        let mut rng = GlibcRand::new(42);
+        assert_eq!([rng.rand(), rng.rand()], [71876166, 708592740]);
+        assert_eq!(GlibcRand::new(0).rand(), 1804289383);
+
+        let mut rng = GlibcRand::new(5);
+        let v = (0..10000).map(|_| rng.rand()).last();
+        assert_eq!(v, Some(1716686462));
+    }
+
+    #[test]
+    fn ansi_c_rand() {
+        let mut rng = AnsiCRand::default();
+        assert_eq!([rng.rand(), rng.rand(), rng.rand()], [16838, 5758, 10113]);
+    }
+
+    #[test]
+    fn words_are_built_from_outputs() {
+        let mut a = GlibcRand::new(1);
+        assert_eq!(a.calc(), (1804289383 >> 15) << 16 | 846930886 >> 15);
+
+        let mut a = AnsiCRand::default();
+        assert_eq!(a.calc(), 16838 << 17 | 5758 << 2 | 10113 >> 13);
+
+        let mut a = MinStdRand::new(0);
+        let mut b = a;
+        let (hi, lo) = (b.next_raw(), b.next_raw());
+        assert_eq!(a.calc(), (hi >> 15) << 16 | lo >> 15);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn typed_values_cover_their_range() {
+        fn check<G: PrngCore<Word = u32>>(mut rng: G, name: &str) {
+            let mut bytes = [false; 256];
+            let mut bools = [false; 2];
+            let mut dice = [false; 6];
+            for _ in 0..10_000 {
+                bytes[rng.next::<u8>() as usize] = true;
+                bools[rng.next::<bool>() as usize] = true;
+                dice[rng.next_range(1..=6_usize) - 1] = true;
+            }
+            assert!(bytes.iter().all(|b| *b), "{name} u8");
+            assert_eq!(bools, [true; 2], "{name} bool");
+            assert_eq!(dice, [true; 6], "{name} 1..=6");
+        }
+        check(MinStdRand0::new(1), "minstd_rand0");
+        check(MinStdRand::new(1), "minstd_rand");
+        check(Drand48::new(1), "drand48");
+        check(GlibcRand::new(1), "glibc_rand");
+        check(AnsiCRand::new(1), "ansi_c_rand");
+
+        let v: std::num::NonZeroU8 = AnsiCRand::new(2).next();
+        assert_ne!(v.get(), 0);
+    }
+
+    #[test]
+    fn skip_ahead_matches_stepping() {
+        let mut a = Drand48::new(3);
+        let mut b = a;
+        for _ in 0..12345 {
+            a.calc();
+        }
+        b.advance(12345);
+        assert_eq!(a, b);
+
+        let mut a = MinStdRand0::new(3);
+        let mut b = a;
+        for _ in 0..777 {
+            a.next_raw();
+        }
+        b.advance(777);
+        assert_eq!(a, b);
+
+        // A word is three steps.
+        let mut a = AnsiCRand::new(3);
+        let mut b = a;
+        for _ in 0..1000 {
+            a.calc();
+        }
+        b.advance(3000);
+        assert_eq!(a, b);
+
+        // The full period of the 48-bit LCG brings it back.
+        let mut c = Drand48::new(9);
+        c.advance(1 << 48);
+        assert_eq!(c, Drand48::new(9));
+    }
+
+    #[test]
+    fn seedable() {
+        assert_eq!(
+            MinStdRand0::from_seed(7_u32.to_le_bytes()),
+            MinStdRand0::new(7)
+        );
+        assert_eq!(MinStdRand::from_seed([0; 4]), MinStdRand::new(1));
+        assert_eq!(
+            MinStdRand::from_seed([0xff, 0xff, 0xff, 0x7f]),
+            MinStdRand::new(1)
+        );
+        assert_eq!(
+            Drand48::from_seed([0x0e, 0x33, 42, 0, 0, 0]),
+            Drand48::new(42)
+        );
+        assert_eq!(
+            GlibcRand::from_seed(42_u32.to_le_bytes()),
+            GlibcRand::new(42)
+        );
+        assert_eq!(AnsiCRand::from_seed(9_u32.to_le_bytes()), AnsiCRand::new(9));
+
+        // Seeds from strings land in the valid state spaces too.
+        for label in ["", "a", "level-3/boss"] {
+            let mut rng = MinStdRand0::from_seed_str(label);
+            assert!((1..0x7fff_ffff).contains(&rng.next_raw()));
+        }
+        assert_ne!(
+            Drand48::from_entropy().unwrap(),
+            Drand48::from_entropy().unwrap()
+        );
+    }
+
+    #[test]
+    fn state_round_trips() {
+        let mut rng = GlibcRand::new(8);
+        rng.rand();
+        let mut restored = GlibcRand::from_bytes(&rng.to_bytes()).unwrap();
+        assert_eq!(rng.rand(), restored.rand());
+
+        let bytes = MinStdRand::new(4).to_bytes();
+        assert!(MinStdRand0::from_bytes(&bytes).is_err());
+        assert_eq!(MinStdRand::from_bytes(&bytes).unwrap(), MinStdRand::new(4));
+    }
+
+    #[test]
+    fn dump_value_types() {
+        let mut rng = Drand48::new(123456);
+
+        let a: u8 = rng.next();
+        assert_eq!(a, 240);
+
+        let b: u128 = rng.next();
+        assert_eq!(b, 18843228225711553359660188317806616513);
+
+        let c: bool = rng.next();
+        assert!(!c);
+
+        let d: f32 = rng.next();
+        assert_eq!(d, 0.11109771);
+    }
+}
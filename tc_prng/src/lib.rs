@@ -1,11 +1,45 @@
+pub mod bits;
+pub mod chacha;
+pub mod check;
+pub mod entropy;
+pub mod jsf;
+pub mod lanes;
+pub mod lcg;
+pub mod mock;
+pub mod mt;
+pub mod noise;
+pub mod normal;
+pub mod par;
+pub mod philox;
+pub mod positional;
+pub mod quality;
+pub mod range;
+pub mod replay;
+pub mod romu;
+pub mod seed_seq;
+pub mod sfc;
+pub mod state;
 pub mod split_mix;
+pub mod thread;
+pub mod threefry;
+pub mod wyrand;
 pub mod xorshiro;
+mod compound;
 mod prng_32;
 mod prng_64;
 mod prng_128;
 
 pub use xorshiro::xorshiro;
 pub use split_mix::split_mix;
+pub use prng_32::Prng32;
+pub use prng_64::Prng64;
+pub use prng_128::Prng128;
+pub use entropy::EntropyError;
+pub use seed_seq::SeedSequence;
+pub use range::SampleRange;
+pub use thread::{random, random_range, seed_thread_rng, thread_rng, ThreadRng};
+pub use state::{Persist, StateError};
+pub use tc_prng_derive::Random;
 
 pub mod prelude {
     pub use super::{
@@ -13,6 +47,99 @@ pub mod prelude {
         split_mix,
         xorshiro::Xorshiro64,
         split_mix::SplitMix64,
+        Prng,
+        PrngCore,
+        Seedable,
     };
 }
 
+/// A generator producing raw words of a fixed width.
+///
+/// This is the single extension point of the crate: anything implementing
+/// `PrngCore` gets every typed conversion for its word width through
+/// [`Prng`], so generic code can accept any generator with a
+/// `G: PrngCore` bound.
+pub trait PrngCore {
+    type Word;
+
+    /// Advances the generator and returns the next raw word.
+    fn calc(&mut self) -> Self::Word;
+
+    /// Produces a value of the requested type.
+    fn next<T>(&mut self) -> T
+    where
+        T: FromWord<Self::Word>,
+    {
+        T::from_word(self)
+    }
+
+    /// Produces a value uniformly distributed over `range`.
+    fn next_range<T, R>(&mut self, range: R) -> T
+    where
+        R: SampleRange<T>,
+        Self: Prng<u64>,
+    {
+        range.sample(self)
+    }
+}
+
+/// A generator able to produce values of type `T`.
+pub trait Prng<T> {
+    fn next_val(&mut self) -> T;
+}
+
+/// Builds a value of `Self` from the raw words of a generator of width `W`.
+///
+/// Each word width defines its own table of conversions (see `Prng32`,
+/// `Prng64` and `Prng128`), which is what decides how many words a value
+/// consumes and which bits are kept. Arrays, tuples, `Option`, `char` and
+/// the other compound types are built on top of those and draw their parts
+/// in a fixed order, see the `compound` module source.
+pub trait FromWord<W>: Sized {
+    fn from_word<G: PrngCore<Word = W> + ?Sized>(rng: &mut G) -> Self;
+}
+
+impl<G, T> Prng<T> for G
+where
+    G: PrngCore + ?Sized,
+    T: FromWord<G::Word>,
+{
+    fn next_val(&mut self) -> T {
+        T::from_word(self)
+    }
+}
+
+/// Generators that can be built from raw seed bytes.
+pub trait Seedable: Sized {
+    /// Little-endian seed material, e.g. `[u8; 8]` for a 64-bit state.
+    type Seed: Default + AsMut<[u8]>;
+
+    fn from_seed(seed: Self::Seed) -> Self;
+
+    /// Seeds the generator from the operating system, see [`entropy::entropy`].
+    ///
+    /// Cryptographic generators override this to use only
+    /// [`entropy::os_entropy`], never the guessable fallback.
+    fn from_entropy() -> Result<Self, EntropyError> {
+        let mut seed = Self::Seed::default();
+        entropy::entropy(seed.as_mut())?;
+        Ok(Self::from_seed(seed))
+    }
+
+    /// Seeds the generator with words drawn from `seq`.
+    fn from_seed_sequence(seq: &SeedSequence) -> Self {
+        let mut seed = Self::Seed::default();
+        seq.fill_bytes(seed.as_mut());
+        Self::from_seed(seed)
+    }
+
+    /// Seeds the generator from arbitrary bytes, see [`SeedSequence::from_bytes`].
+    fn from_seed_bytes(bytes: &[u8]) -> Self {
+        Self::from_seed_sequence(&SeedSequence::from_bytes(bytes))
+    }
+
+    /// Seeds the generator from a label such as `"level-3/boss"`.
+    fn from_seed_str(s: &str) -> Self {
+        Self::from_seed_bytes(s.as_bytes())
+    }
+}
@@ -0,0 +1,196 @@
+//! Deterministic generators for unit tests.
+//!
+//! Both produce raw words chosen by the test and implement [`PrngCore`], so
+//! every typed conversion behaves exactly as it would on a real generator of
+//! the same width.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tc_prng::mock::{ScriptedRng, StepRng};
+//!
+//! // A u64 generator whose next two words are 0 and u64::MAX.
+//! let mut rng = ScriptedRng::new(vec![0_u64, u64::MAX]);
+//! assert_eq!(rng.next::<f64>(), 0.0);
+//! assert!(rng.next::<bool>());
+//!
+//! let mut rng = StepRng::new(1_u32, 2);
+//! assert_eq!([rng.next::<u32>(), rng.next(), rng.next()], [1, 3, 5]);
+//! ```
+
+use crate::{Prng, PrngCore};
+
+/// Returns a fixed sequence of raw words.
+///
+/// Created with [`new`](Self::new) it panics once the script is used up,
+/// which catches code drawing more values than the test expects; created
+/// with [`cycle`](Self::cycle) it starts over instead.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScriptedRng<W> {
+    words: Vec<W>,
+    pos: usize,
+    cycle: bool,
+}
+
+impl<W: Copy> ScriptedRng<W> {
+    pub fn new(words: Vec<W>) -> Self {
+        Self {
+            words,
+            pos: 0,
+            cycle: false,
+        }
+    }
+
+    /// Repeats `words` forever. Panics if `words` is empty.
+    pub fn cycle(words: Vec<W>) -> Self {
+        assert!(!words.is_empty(), "cannot cycle an empty script");
+        Self {
+            words,
+            pos: 0,
+            cycle: true,
+        }
+    }
+
+    /// Number of words handed out so far, counting every repetition.
+    pub fn consumed(&self) -> usize {
+        self.pos
+    }
+
+    /// Words left before the script is used up or starts over.
+    pub fn remaining(&self) -> usize {
+        match self.words.len() {
+            0 => 0,
+            n if self.cycle => n - self.pos % n,
+            n => n - self.pos,
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<V>(&mut self) -> V
+    where
+        Self: Prng<V>,
+    {
+        Prng::next_val(self)
+    }
+
+    fn word(&mut self) -> W {
+        let i = if self.cycle {
+            self.pos % self.words.len()
+        } else {
+            self.pos
+        };
+        let Some(w) = self.words.get(i) else {
+            panic!(
+                "ScriptedRng exhausted after {} words; script more words or use `ScriptedRng::cycle`",
+                self.pos
+            );
+        };
+        self.pos += 1;
+        *w
+    }
+}
+
+/// Returns `start`, `start + step`, `start + 2 * step`, ... wrapping on
+/// overflow.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct StepRng<W> {
+    value: W,
+    step: W,
+}
+
+impl<W> StepRng<W> {
+    pub fn new(start: W, step: W) -> Self {
+        Self { value: start, step }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<V>(&mut self) -> V
+    where
+        Self: Prng<V>,
+    {
+        Prng::next_val(self)
+    }
+}
+
+macro_rules! impl_mock {
+    ($($t:ty),*) => {$(
+        impl PrngCore for ScriptedRng<$t> {
+            type Word = $t;
+
+            fn calc(&mut self) -> $t {
+                self.word()
+            }
+        }
+
+        impl PrngCore for StepRng<$t> {
+            type Word = $t;
+
+            fn calc(&mut self) -> $t {
+                let v = self.value;
+                self.value = v.wrapping_add(self.step);
+                v
+            }
+        }
+    )*};
+}
+
+impl_mock!(u32, u64, u128);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scripted_words() {
+        let mut rng = ScriptedRng::new(vec![1_u32, 2, 3]);
+        assert_eq!(rng.remaining(), 3);
+        assert_eq!(rng.next::<u32>(), 1);
+        // A u64 from 32-bit words takes two of them.
+        assert_eq!(rng.next::<u64>(), rng_u64(2, 3));
+        assert_eq!(rng.consumed(), 3);
+        assert_eq!(rng.remaining(), 0);
+    }
+
+    fn rng_u64(a: u32, b: u32) -> u64 {
+        ScriptedRng::new(vec![a, b]).next()
+    }
+
+    #[test]
+    #[should_panic(expected = "ScriptedRng exhausted after 1 words")]
+    fn scripted_panics_when_exhausted() {
+        let mut rng = ScriptedRng::new(vec![7_u64]);
+        let _: u8 = rng.next();
+        let _: u8 = rng.next();
+    }
+
+    #[test]
+    fn scripted_cycles() {
+        let mut rng = ScriptedRng::cycle(vec![0_u64, u64::MAX]);
+        let v: Vec<bool> = (0..5).map(|_| rng.next()).collect();
+        assert_eq!(v, [false, true, false, true, false]);
+        assert_eq!(rng.consumed(), 5);
+        assert_eq!(rng.remaining(), 1);
+    }
+
+    #[test]
+    fn drives_conversions_to_their_bounds() {
+        let mut rng = ScriptedRng::new(vec![0_u64, u64::MAX]);
+        assert_eq!(rng.next::<f64>(), 0.0);
+        // The float conversions include both ends of [0, 1].
+        assert_eq!(rng.next::<f64>(), 1.0);
+
+        let mut rng = ScriptedRng::new(vec![u32::MAX]);
+        assert_eq!(rng.next::<i32>(), -1);
+    }
+
+    #[test]
+    fn steps_and_wraps() {
+        let mut rng = StepRng::new(u64::MAX - 1, 1);
+        let v: Vec<u64> = (0..3).map(|_| rng.next()).collect();
+        assert_eq!(v, [u64::MAX - 1, u64::MAX, 0]);
+
+        let mut rng = StepRng::new(0_u128, 10);
+        assert_eq!(rng.next::<u128>(), 0);
+        assert_eq!(rng.next::<u128>(), 10);
+    }
+}
@@ -0,0 +1,435 @@
+//! Mersenne Twister, for sequences shared with other tools.
+//!
+//! [`Mt19937`] and [`Mt19937_64`] follow Matsumoto and Nishimura's reference
+//! code (`mt19937ar.c` and `mt19937-64.c`) word for word, so the same seed
+//! gives the same raw words as C++'s `std::mt19937` / `std::mt19937_64` and
+//! Python's `random` module:
+//!
+//! - `std::mt19937 rng(s)` is `Mt19937::new(s)` (`init_genrand`).
+//! - Python's `random.seed(n)` is `Mt19937::from_key` with the 32-bit words
+//!   of `n`, least significant first (`init_by_array`), and
+//!   `random.getrandbits(32)` returns the raw words.
+//!
+//! Typed values from `next::<T>()` use this crate's conversions, so only the
+//! raw words (`next::<u32>()` / `next::<u64>()`) match other libraries.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tc_prng::mt::Mt19937;
+//!
+//! // Same as C++ `std::mt19937 rng(42); rng();`
+//! let mut rng = Mt19937::new(42);
+//! assert_eq!(rng.next::<u32>(), 1608637542);
+//! ```
+
+use crate::{
+    state::{Algorithm, Codec, Persist, Reader, StateError},
+    Prng, PrngCore, Seedable,
+};
+
+/// Position of a Mersenne Twister, see [`Persist`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MtState<W, const N: usize> {
+    pub mt: [W; N],
+    /// Index of the next word of `mt` to temper; `N` means a twist is due.
+    pub index: usize,
+}
+
+/// 32-bit Mersenne Twister MT19937.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Mt19937 {
+    mt: [u32; 624],
+    idx: usize,
+}
+
+/// 64-bit Mersenne Twister MT19937-64.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Mt19937_64 {
+    mt: [u64; 312],
+    idx: usize,
+}
+
+impl Default for Mt19937 {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SEED)
+    }
+}
+
+impl Default for Mt19937_64 {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SEED)
+    }
+}
+
+impl Seedable for Mt19937 {
+    type Seed = [u8; 4];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(u32::from_le_bytes(seed))
+    }
+}
+
+impl Seedable for Mt19937_64 {
+    type Seed = [u8; 8];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(u64::from_le_bytes(seed))
+    }
+}
+
+impl Mt19937 {
+    const N: usize = 624;
+    const M: usize = 397;
+    const MATRIX_A: u32 = 0x9908b0df;
+    const UPPER: u32 = 0x80000000;
+    const LOWER: u32 = 0x7fffffff;
+
+    /// The seed used by C++'s default-constructed `std::mt19937`.
+    pub const DEFAULT_SEED: u32 = 5489;
+
+    /// Seeds like `init_genrand`.
+    pub fn new(seed: u32) -> Self {
+        let mut mt = [0; 624];
+        mt[0] = seed;
+        for i in 1..Self::N {
+            let prev = mt[i - 1];
+            mt[i] = 1812433253_u32
+                .wrapping_mul(prev ^ (prev >> 30))
+                .wrapping_add(i as u32);
+        }
+        Self { mt, idx: Self::N }
+    }
+
+    /// Seeds like `init_by_array`. Panics if `key` is empty.
+    pub fn from_key(key: &[u32]) -> Self {
+        assert!(!key.is_empty(), "init_by_array needs at least one word");
+        let n = Self::N;
+        let mut rng = Self::new(19650218);
+        let mt = &mut rng.mt;
+        let (mut i, mut j) = (1, 0);
+        for _ in 0..n.max(key.len()) {
+            let prev = mt[i - 1];
+            mt[i] = (mt[i] ^ (prev ^ (prev >> 30)).wrapping_mul(1664525))
+                .wrapping_add(key[j])
+                .wrapping_add(j as u32);
+            i += 1;
+            j += 1;
+            if i >= n {
+                mt[0] = mt[n - 1];
+                i = 1;
+            }
+            if j >= key.len() {
+                j = 0;
+            }
+        }
+        for _ in 0..n - 1 {
+            let prev = mt[i - 1];
+            mt[i] = (mt[i] ^ (prev ^ (prev >> 30)).wrapping_mul(1566083941)).wrapping_sub(i as u32);
+            i += 1;
+            if i >= n {
+                mt[0] = mt[n - 1];
+                i = 1;
+            }
+        }
+        mt[0] = 0x80000000;
+        rng
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<V>(&mut self) -> V
+    where
+        Self: Prng<V>,
+    {
+        Prng::next_val(self)
+    }
+
+    fn twist(&mut self) {
+        let mt = &mut self.mt;
+        for i in 0..Self::N {
+            let y = (mt[i] & Self::UPPER) | (mt[(i + 1) % Self::N] & Self::LOWER);
+            let mag = if y & 1 == 1 { Self::MATRIX_A } else { 0 };
+            mt[i] = mt[(i + Self::M) % Self::N] ^ (y >> 1) ^ mag;
+        }
+        self.idx = 0;
+    }
+}
+
+impl Mt19937_64 {
+    const N: usize = 312;
+    const M: usize = 156;
+    const MATRIX_A: u64 = 0xB5026F5AA96619E9;
+    const UPPER: u64 = 0xFFFFFFFF80000000;
+    const LOWER: u64 = 0x7FFFFFFF;
+
+    /// The seed used by C++'s default-constructed `std::mt19937_64`.
+    pub const DEFAULT_SEED: u64 = 5489;
+
+    /// Seeds like `init_genrand64`.
+    pub fn new(seed: u64) -> Self {
+        let mut mt = [0; 312];
+        mt[0] = seed;
+        for i in 1..Self::N {
+            let prev = mt[i - 1];
+            mt[i] = 6364136223846793005_u64
+                .wrapping_mul(prev ^ (prev >> 62))
+                .wrapping_add(i as u64);
+        }
+        Self { mt, idx: Self::N }
+    }
+
+    /// Seeds like `init_by_array64`. Panics if `key` is empty.
+    pub fn from_key(key: &[u64]) -> Self {
+        assert!(!key.is_empty(), "init_by_array64 needs at least one word");
+        let n = Self::N;
+        let mut rng = Self::new(19650218);
+        let mt = &mut rng.mt;
+        let (mut i, mut j) = (1, 0);
+        for _ in 0..n.max(key.len()) {
+            let prev = mt[i - 1];
+            mt[i] = (mt[i] ^ (prev ^ (prev >> 62)).wrapping_mul(3935559000370003845))
+                .wrapping_add(key[j])
+                .wrapping_add(j as u64);
+            i += 1;
+            j += 1;
+            if i >= n {
+                mt[0] = mt[n - 1];
+                i = 1;
+            }
+            if j >= key.len() {
+                j = 0;
+            }
+        }
+        for _ in 0..n - 1 {
+            let prev = mt[i - 1];
+            mt[i] = (mt[i] ^ (prev ^ (prev >> 62)).wrapping_mul(2862933555777941757))
+                .wrapping_sub(i as u64);
+            i += 1;
+            if i >= n {
+                mt[0] = mt[n - 1];
+                i = 1;
+            }
+        }
+        mt[0] = 1 << 63;
+        rng
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<V>(&mut self) -> V
+    where
+        Self: Prng<V>,
+    {
+        Prng::next_val(self)
+    }
+
+    fn twist(&mut self) {
+        let mt = &mut self.mt;
+        for i in 0..Self::N {
+            let x = (mt[i] & Self::UPPER) | (mt[(i + 1) % Self::N] & Self::LOWER);
+            let mag = if x & 1 == 1 { Self::MATRIX_A } else { 0 };
+            mt[i] = mt[(i + Self::M) % Self::N] ^ (x >> 1) ^ mag;
+        }
+        self.idx = 0;
+    }
+}
+
+impl PrngCore for Mt19937 {
+    type Word = u32;
+
+    fn calc(&mut self) -> u32 {
+        if self.idx >= Self::N {
+            self.twist();
+        }
+        let mut y = self.mt[self.idx];
+        self.idx += 1;
+        y ^= y >> 11;
+        y ^= (y << 7) & 0x9d2c5680;
+        y ^= (y << 15) & 0xefc60000;
+        y ^ (y >> 18)
+    }
+}
+
+impl PrngCore for Mt19937_64 {
+    type Word = u64;
+
+    fn calc(&mut self) -> u64 {
+        if self.idx >= Self::N {
+            self.twist();
+        }
+        let mut x = self.mt[self.idx];
+        self.idx += 1;
+        x ^= (x >> 29) & 0x5555555555555555;
+        x ^= (x << 17) & 0x71D67FFFEDA60000;
+        x ^= (x << 37) & 0xFFF7EEE000000000;
+        x ^ (x >> 43)
+    }
+}
+
+impl Persist for Mt19937 {
+    type State = MtState<u32, 624>;
+
+    const ALGORITHM: Algorithm = Algorithm::Mt19937;
+    const VARIANT: u8 = 32;
+    const STATE_LEN: usize = 624 * 4 + 4;
+
+    fn state(&self) -> Self::State {
+        MtState {
+            mt: self.mt,
+            index: self.idx,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Self {
+        Self {
+            mt: state.mt,
+            idx: state.index.min(Self::N),
+        }
+    }
+}
+
+impl Codec for Mt19937 {
+    fn encode(&self, out: &mut Vec<u8>) {
+        for w in self.mt {
+            out.extend_from_slice(&w.to_le_bytes());
+        }
+        out.extend_from_slice(&(self.idx as u32).to_le_bytes());
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, StateError> {
+        let mut r = Reader(bytes);
+        let mt = std::array::from_fn(|_| r.u32());
+        let index = r.u32() as usize;
+        Ok(Self::from_state(MtState { mt, index }))
+    }
+}
+
+impl Persist for Mt19937_64 {
+    type State = MtState<u64, 312>;
+
+    const ALGORITHM: Algorithm = Algorithm::Mt19937;
+    const VARIANT: u8 = 64;
+    const STATE_LEN: usize = 312 * 8 + 4;
+
+    fn state(&self) -> Self::State {
+        MtState {
+            mt: self.mt,
+            index: self.idx,
+        }
+    }
+
+    fn from_state(state: Self::State) -> Self {
+        Self {
+            mt: state.mt,
+            idx: state.index.min(Self::N),
+        }
+    }
+}
+
+impl Codec for Mt19937_64 {
+    fn encode(&self, out: &mut Vec<u8>) {
+        for w in self.mt {
+            out.extend_from_slice(&w.to_le_bytes());
+        }
+        out.extend_from_slice(&(self.idx as u32).to_le_bytes());
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, StateError> {
+        let mut r = Reader(bytes);
+        let mt = std::array::from_fn(|_| r.u64());
+        let index = r.u32() as usize;
+        Ok(Self::from_state(MtState { mt, index }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cpp_standard_10000th_output() {
+        // [rand.predef] in the C++ standard.
+        let mut rng = Mt19937::default();
+        let v = (0..10000).map(|_| rng.next::<u32>()).last();
+        assert_eq!(v, Some(4123659995));
+
+        let mut rng = Mt19937_64::default();
+        let v = (0..10000).map(|_| rng.next::<u64>()).last();
+        assert_eq!(v, Some(9981545732273789042));
+    }
+
+    #[test]
+    fn init_genrand_matches_cpp() {
+        let mut rng = Mt19937::new(42);
+        assert_eq!([rng.calc(), rng.calc()], [1608637542, 3421126067]);
+
+        let mut rng = Mt19937_64::new(42);
+        assert_eq!(
+            [rng.calc(), rng.calc()],
+            [13930160852258120406, 11788048577503494824]
+        );
+    }
+
+    #[test]
+    fn init_by_array_reference() {
+        // mt19937ar.out and mt19937-64.out.
+        let mut rng = Mt19937::from_key(&[0x123, 0x234, 0x345, 0x456]);
+        let v: Vec<u32> = (0..5).map(|_| rng.next()).collect();
+        assert_eq!(
+            v,
+            [1067595299, 955945823, 477289528, 4107218783, 4228976476]
+        );
+
+        let mut rng = Mt19937_64::from_key(&[0x12345, 0x23456, 0x34567, 0x45678]);
+        let v: Vec<u64> = (0..3).map(|_| rng.next()).collect();
+        assert_eq!(
+            v,
+            [
+                7266447313870364031,
+                4946485549665804864,
+                16945909448695747420
+            ]
+        );
+    }
+
+    #[test]
+    fn python_random_seed() {
+        // random.seed(12345); [random.getrandbits(32) for _ in range(3)]
+        let mut rng = Mt19937::from_key(&[12345]);
+        let v: Vec<u32> = (0..3).map(|_| rng.next()).collect();
+        assert_eq!(v, [1789368711, 3146859322, 43676229]);
+    }
+
+    #[test]
+    fn state_round_trip_across_twist() {
+        let mut rng = Mt19937::new(7);
+        for _ in 0..620 {
+            rng.calc();
+        }
+        let mut restored = Mt19937::from_bytes(&rng.to_bytes()).unwrap();
+        for _ in 0..10 {
+            assert_eq!(rng.calc(), restored.calc());
+        }
+
+        let mut rng = Mt19937_64::new(7);
+        rng.calc();
+        let mut restored = Mt19937_64::from_state(rng.state());
+        assert_eq!(rng.calc(), restored.calc());
+    }
+
+    #[test]
+    fn dump_value_types() {
+        let mut rng = Mt19937::new(123456);
+
+        let a: u8 = rng.next();
+        assert_eq!(a, 32);
+
+        let b: u128 = rng.next();
+        assert_eq!(b, 88635391985287153824746994558110358250);
+
+        let c: bool = rng.next();
+        assert!(!c);
+
+        let d: f32 = rng.next();
+        assert_eq!(d, 0.44861823);
+    }
+}
@@ -0,0 +1,115 @@
+//! Coherent noise seeded from the crate's generators.
+//!
+//! All noise functions work on `f64` and only use operations IEEE 754
+//! requires to be correctly rounded (`+`, `-`, `*`, `/`, `sqrt`) plus
+//! `floor`, so a given seed and input produce bit-identical output on every
+//! platform.
+
+mod cellular;
+mod fractal;
+mod open_simplex;
+mod perlin;
+mod simplex;
+
+use crate::{split_mix::SplitMix64, Prng};
+
+pub use cellular::{CellSample, Distance, Worley};
+pub use fractal::{Fractal, FractalKind};
+pub use open_simplex::OpenSimplex2;
+pub use perlin::Perlin;
+pub use simplex::Simplex;
+
+/// A noise function sampled at an `N`-dimensional point.
+pub trait NoiseFn<const N: usize> {
+    fn sample(&self, point: [f64; N]) -> f64;
+}
+
+impl<S, const N: usize> NoiseFn<N> for &S
+where
+    S: NoiseFn<N> + ?Sized,
+{
+    fn sample(&self, point: [f64; N]) -> f64 {
+        (**self).sample(point)
+    }
+}
+
+/// Shuffled lattice hash table shared by the gradient noises.
+///
+/// The 256 entry permutation is stored twice so nested lookups such as
+/// `perm[perm[x] + y]` never need to wrap.
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) struct Permutation([u8; 512]);
+
+impl Permutation {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self::from_rng(&mut SplitMix64::new(seed))
+    }
+
+    pub(crate) fn from_rng<G: Prng<u32> + ?Sized>(rng: &mut G) -> Self {
+        let mut p = [0u8; 512];
+        for (i, v) in p.iter_mut().take(256).enumerate() {
+            *v = i as u8;
+        }
+
+        // Fisher-Yates
+        for i in (1..256).rev() {
+            let j = bounded(rng, i as u32 + 1) as usize;
+            p.swap(i, j);
+        }
+
+        let (lo, hi) = p.split_at_mut(256);
+        hi.copy_from_slice(lo);
+        Self(p)
+    }
+
+    #[inline]
+    pub(crate) fn hash(&self, i: i64) -> usize {
+        self.0[(i & 255) as usize] as usize
+    }
+
+    #[inline]
+    pub(crate) fn get(&self, i: usize) -> usize {
+        self.0[i] as usize
+    }
+}
+
+/// Unbiased value in `0..n` (Lemire's multiply and reject).
+fn bounded<G: Prng<u32> + ?Sized>(rng: &mut G, n: u32) -> u32 {
+    let threshold = n.wrapping_neg() % n;
+    loop {
+        let v: u32 = rng.next_val();
+        let m = v as u64 * n as u64;
+        if (m as u32) >= threshold {
+            return (m >> 32) as u32;
+        }
+    }
+}
+
+#[inline]
+pub(crate) fn floor(v: f64) -> i64 {
+    v.floor() as i64
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn permutation_is_bijective() {
+        let p = Permutation::from_rng(&mut xorshiro(99_u32));
+        let mut seen = [false; 256];
+        for i in 0..256 {
+            seen[p.get(i)] = true;
+            assert_eq!(p.get(i), p.get(i + 256));
+        }
+        assert!(seen.iter().all(|s| *s));
+    }
+
+    #[test]
+    fn permutation_follows_seed() {
+        assert!(Permutation::new(1) == Permutation::new(1));
+        assert!(Permutation::new(1) != Permutation::new(2));
+        assert!(Permutation::new(7) == Permutation::from_rng(&mut split_mix(7_u64)));
+    }
+}
@@ -0,0 +1,273 @@
+use super::{floor, NoiseFn};
+use crate::split_mix::SplitMix64;
+
+/// Distance metric used to measure the distance to feature points.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Distance {
+    Euclidean,
+    Manhattan,
+    Chebyshev,
+}
+
+impl Distance {
+    fn measure<const N: usize>(self, d: [f64; N]) -> f64 {
+        match self {
+            Distance::Euclidean => d.iter().map(|v| v * v).sum::<f64>().sqrt(),
+            Distance::Manhattan => d.iter().map(|v| v.abs()).sum(),
+            Distance::Chebyshev => d.iter().fold(0.0, |m, v| v.abs().max(m)),
+        }
+    }
+}
+
+/// Result of a cellular noise lookup.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CellSample {
+    /// Distance to the closest feature point.
+    pub f1: f64,
+    /// Distance to the second closest feature point.
+    pub f2: f64,
+    /// Hash of the cell owning the closest feature point, stable for a seed.
+    pub cell: u64,
+}
+
+impl CellSample {
+    pub fn f2_minus_f1(&self) -> f64 {
+        self.f2 - self.f1
+    }
+}
+
+/// Worley (cellular / Voronoi) noise in two and three dimensions.
+///
+/// Every integer cell holds one feature point whose position comes from
+/// hashing the seed and the cell coordinates with the `SplitMix64` mixer, so
+/// the pattern only depends on the seed and never on lookup order.
+///
+/// # Examples
+///
+/// ```rust
+/// use tc_prng::noise::{Distance, Worley};
+///
+/// let biomes = Worley::new(42).distance(Distance::Manhattan);
+/// let s = biomes.cell2(10.3, -4.8);
+/// assert!(s.f1 <= s.f2);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Worley {
+    seed: u64,
+    distance: Distance,
+    jitter: f64,
+}
+
+impl Worley {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            distance: Distance::Euclidean,
+            jitter: 1.0,
+        }
+    }
+
+    pub fn distance(mut self, distance: Distance) -> Self {
+        self.distance = distance;
+        self
+    }
+
+    /// How far feature points may stray from the cell center, from `0`
+    /// (regular grid) to `1` (anywhere in the cell), clamped to that range.
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn distance_metric(&self) -> Distance {
+        self.distance
+    }
+
+    /// The jitter in `[0, 1]`.
+    pub fn jitter_amount(&self) -> f64 {
+        self.jitter
+    }
+
+    pub fn cell2(&self, x: f64, y: f64) -> CellSample {
+        self.search([x, y])
+    }
+
+    pub fn cell3(&self, x: f64, y: f64, z: f64) -> CellSample {
+        self.search([x, y, z])
+    }
+
+    fn cell_hash<const N: usize>(&self, cell: [i64; N]) -> u64 {
+        cell.iter().fold(SplitMix64::mix(self.seed), |h, c| {
+            SplitMix64::mix(h ^ *c as u64)
+        })
+    }
+
+    fn feature<const N: usize>(&self, cell: [i64; N], hash: u64) -> [f64; N] {
+        let mut axis = 0;
+        cell.map(|c| {
+            axis += 1;
+            let r = SplitMix64::mix(hash.wrapping_add(axis));
+            // 53 bit fraction in [0, 1)
+            let r = (r >> 11) as f64 * (1.0 / (1u64 << 53) as f64);
+            c as f64 + 0.5 + (r - 0.5) * self.jitter
+        })
+    }
+
+    /// Visits cells in rings of growing Chebyshev radius around the sample
+    /// and stops once no unvisited cell can beat the current F2.
+    fn search<const N: usize>(&self, p: [f64; N]) -> CellSample {
+        let base = p.map(floor);
+        let mut best = CellSample {
+            f1: f64::INFINITY,
+            f2: f64::INFINITY,
+            cell: 0,
+        };
+
+        let mut ring: i64 = 0;
+        // Any cell in ring r is at least r - 1 away along some axis.
+        while ((ring - 1) as f64) < best.f2 {
+            let side = 2 * ring + 1;
+            for i in 0..side.pow(N as u32) {
+                let mut rem = i;
+                let offset: [i64; N] = [0; N].map(|_| {
+                    let o = rem % side - ring;
+                    rem /= side;
+                    o
+                });
+                if offset.iter().map(|o| o.abs()).max() != Some(ring) {
+                    continue;
+                }
+
+                let mut cell = base;
+                for (c, o) in cell.iter_mut().zip(offset) {
+                    *c += o;
+                }
+                let hash = self.cell_hash(cell);
+                let point = self.feature(cell, hash);
+
+                let mut delta = [0.0; N];
+                for a in 0..N {
+                    delta[a] = point[a] - p[a];
+                }
+                let d = self.distance.measure(delta);
+
+                if d < best.f1 {
+                    best.f2 = best.f1;
+                    best.f1 = d;
+                    best.cell = hash;
+                } else if d < best.f2 {
+                    best.f2 = d;
+                }
+            }
+            ring += 1;
+        }
+        best
+    }
+}
+
+impl NoiseFn<2> for Worley {
+    fn sample(&self, [x, y]: [f64; 2]) -> f64 {
+        self.cell2(x, y).f1
+    }
+}
+
+impl NoiseFn<3> for Worley {
+    fn sample(&self, [x, y, z]: [f64; 3]) -> f64 {
+        self.cell3(x, y, z).f1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ordering_and_determinism() {
+        for metric in [
+            Distance::Euclidean,
+            Distance::Manhattan,
+            Distance::Chebyshev,
+        ] {
+            let a = Worley::new(77).distance(metric);
+            let b = Worley::new(77).distance(metric);
+            for i in 0..300 {
+                let (x, y, z) = (i as f64 * 0.37, i as f64 * -0.21, i as f64 * 0.05);
+                let s = a.cell3(x, y, z);
+                assert!(0.0 <= s.f1 && s.f1 <= s.f2);
+                assert_eq!(s, b.cell3(x, y, z));
+                assert_eq!(a.cell2(x, y), b.cell2(x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for metric in [
+            Distance::Euclidean,
+            Distance::Manhattan,
+            Distance::Chebyshev,
+        ] {
+            let w = Worley::new(3).distance(metric);
+            for i in 0..200 {
+                let p = [i as f64 * 0.731, i as f64 * -0.457];
+                let base = p.map(floor);
+                let mut d: Vec<f64> = Vec::new();
+                for dx in -5..=5 {
+                    for dy in -5..=5 {
+                        let cell = [base[0] + dx, base[1] + dy];
+                        let f = w.feature(cell, w.cell_hash(cell));
+                        d.push(metric.measure([f[0] - p[0], f[1] - p[1]]));
+                    }
+                }
+                d.sort_by(f64::total_cmp);
+                let s = w.cell2(p[0], p[1]);
+                assert_eq!((s.f1, s.f2), (d[0], d[1]));
+            }
+        }
+    }
+
+    #[test]
+    fn regular_grid() {
+        let w = Worley::new(1).jitter(0.0);
+        let s = w.cell2(0.5, 0.5);
+        assert_eq!(s.f1, 0.0);
+        assert_eq!(s.f2, 1.0);
+
+        let s = w.distance(Distance::Manhattan).cell3(1.0, 1.0, 1.0);
+        assert_eq!(s.f1, 1.5);
+        assert_eq!(s.f2, 1.5);
+    }
+
+    #[test]
+    fn builder_clamps_jitter() {
+        let w = Worley::new(5).distance(Distance::Chebyshev).jitter(3.0);
+        assert_eq!(w.seed(), 5);
+        assert_eq!(w.distance_metric(), Distance::Chebyshev);
+        assert_eq!(w.jitter_amount(), 1.0);
+        assert_eq!(w.jitter(-1.0).jitter_amount(), 0.0);
+    }
+
+    #[test]
+    fn cell_id_is_shared_within_a_cell() {
+        let w = Worley::new(9).jitter(0.0);
+        assert_eq!(w.cell2(3.1, 4.1).cell, w.cell2(3.9, 4.9).cell);
+        assert_ne!(w.cell2(3.1, 4.1).cell, w.cell2(4.1, 4.1).cell);
+        assert_ne!(
+            w.cell2(3.1, 4.1).cell,
+            Worley::new(10).jitter(0.0).cell2(3.1, 4.1).cell
+        );
+    }
+
+    #[test]
+    fn dump_values() {
+        let w = Worley::new(123456);
+        let s = w.cell2(0.3, 1.7);
+        assert_eq!(s.f1, 0.259850101040462);
+        assert_eq!(s.f2, 0.5449800435335803);
+        assert_eq!(s.cell, 16142635246599007368);
+    }
+}
@@ -0,0 +1,136 @@
+use super::NoiseFn;
+
+/// How the octaves of a [`Fractal`] are combined.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FractalKind {
+    /// Fractal Brownian motion, the plain sum of octaves. Output in `[-1, 1]`.
+    Fbm,
+    /// Sum of `(1 - |n|)^2`, giving sharp crests. Output in `[0, 1]`.
+    Ridged,
+    /// Sum of `|n|`, giving billowy creases. Output in `[0, 1]`.
+    Turbulence,
+}
+
+/// Layers octaves of a source noise at increasing frequency.
+///
+/// Each octave multiplies the frequency by `lacunarity` and the amplitude by
+/// `gain`; the sum is normalized by the total amplitude so the output range
+/// does not depend on the number of octaves.
+///
+/// # Examples
+///
+/// ```rust
+/// use tc_prng::noise::{Fractal, NoiseFn, Simplex};
+///
+/// let terrain = Fractal::fbm(Simplex::new(7)).octaves(5).gain(0.45);
+/// let h = terrain.sample([12.5, -3.0]);
+/// assert!((-1.0..=1.0).contains(&h));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Fractal<S> {
+    pub source: S,
+    pub kind: FractalKind,
+    pub octaves: u32,
+    pub lacunarity: f64,
+    pub gain: f64,
+}
+
+impl<S> Fractal<S> {
+    pub fn new(source: S, kind: FractalKind) -> Self {
+        Self {
+            source,
+            kind,
+            octaves: 6,
+            lacunarity: 2.0,
+            gain: 0.5,
+        }
+    }
+
+    pub fn fbm(source: S) -> Self {
+        Self::new(source, FractalKind::Fbm)
+    }
+
+    pub fn ridged(source: S) -> Self {
+        Self::new(source, FractalKind::Ridged)
+    }
+
+    pub fn turbulence(source: S) -> Self {
+        Self::new(source, FractalKind::Turbulence)
+    }
+
+    pub fn octaves(mut self, octaves: u32) -> Self {
+        self.octaves = octaves;
+        self
+    }
+
+    pub fn lacunarity(mut self, lacunarity: f64) -> Self {
+        self.lacunarity = lacunarity;
+        self
+    }
+
+    pub fn gain(mut self, gain: f64) -> Self {
+        self.gain = gain;
+        self
+    }
+}
+
+impl<S, const N: usize> NoiseFn<N> for Fractal<S>
+where
+    S: NoiseFn<N>,
+{
+    fn sample(&self, point: [f64; N]) -> f64 {
+        let mut sum = 0.0;
+        let mut total = 0.0;
+        let mut amp = 1.0;
+        let mut freq = 1.0;
+
+        for _ in 0..self.octaves {
+            let n = self.source.sample(point.map(|v| v * freq));
+            let n = match self.kind {
+                FractalKind::Fbm => n,
+                FractalKind::Ridged => {
+                    let r = 1.0 - n.abs();
+                    r * r
+                }
+                FractalKind::Turbulence => n.abs(),
+            };
+            sum += n * amp;
+            total += amp;
+            amp *= self.gain;
+            freq *= self.lacunarity;
+        }
+
+        if total == 0.0 {
+            0.0
+        } else {
+            sum / total
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::noise::{Perlin, Simplex};
+
+    #[test]
+    fn single_octave_is_source() {
+        let src = Simplex::new(5);
+        let f = Fractal::fbm(&src).octaves(1);
+        assert_eq!(f.sample([0.4, 2.1]), src.noise2(0.4, 2.1));
+    }
+
+    #[test]
+    fn ranges() {
+        let src = Perlin::new(5);
+        let fbm = Fractal::fbm(&src);
+        let ridged = Fractal::ridged(&src).octaves(4);
+        let turb = Fractal::turbulence(&src).lacunarity(2.5);
+        for i in 0..500 {
+            let p = [i as f64 * 0.173, i as f64 * -0.091, 3.3];
+            assert!((-1.0..=1.0).contains(&fbm.sample(p)));
+            assert!((0.0..=1.0).contains(&ridged.sample(p)));
+            assert!((0.0..=1.0).contains(&turb.sample(p)));
+        }
+    }
+}
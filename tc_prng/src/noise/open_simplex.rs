@@ -0,0 +1,623 @@
+use super::{floor, NoiseFn};
+use crate::Prng;
+
+/// K.jpg's OpenSimplex2 noise in two to four dimensions.
+///
+/// The fast "F" variant: 2D uses the simplex lattice, 3D a rotated
+/// body-centred cubic lattice built from two offset cubic grids and 4D five
+/// offset copies of the A4 lattice. Unlike [`Simplex`](super::Simplex) no
+/// permutation table is shuffled; lattice points are hashed with the 64-bit
+/// seed. 3D and 4D use the reference's fallback orientations, which treat
+/// every axis alike. Output is in `[-1, 1]`.
+///
+/// The reference evaluates in `f32`; this port keeps every step in `f64`,
+/// so values agree with it to within about `1e-5` rather than bit for bit.
+///
+/// # Examples
+///
+/// ```rust
+/// use tc_prng::{noise::OpenSimplex2, split_mix};
+///
+/// let noise = OpenSimplex2::from_rng(&mut split_mix(42_u64));
+/// let v = noise.noise3(0.5, 1.25, -3.0);
+/// assert!((-1.0..=1.0).contains(&v));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct OpenSimplex2 {
+    seed: i64,
+}
+
+impl OpenSimplex2 {
+    const PRIME_X: i64 = 0x5205402b9270c86f;
+    const PRIME_Y: i64 = 0x598cd327003817b5;
+    const PRIME_Z: i64 = 0x5bcc226e9fa0bacb;
+    const PRIME_W: i64 = 0x56cc5227e58f554b;
+    const HASH_MULTIPLIER: i64 = 0x53a3f72deec546f5;
+    const SEED_FLIP_3D: i64 = -0x52d547b2e96ed629;
+    const SEED_OFFSET_4D: i64 = 0xe83dc3e0da7164d;
+
+    const SKEW_2D: f64 = 0.366025403784439;
+    const UNSKEW_2D: f64 = -0.21132486540518713;
+    const FALLBACK_ROTATE_3D: f64 = 2.0 / 3.0;
+    const SKEW_4D: f64 = -0.138196601125011;
+    const UNSKEW_4D: f64 = 0.309016994374947;
+    const LATTICE_STEP_4D: f64 = 0.2;
+
+    const RSQUARED_2D: f64 = 0.5;
+    const RSQUARED_3D: f64 = 0.6;
+    const RSQUARED_4D: f64 = 0.6;
+
+    /// Creates the noise for a seed. The same seed gives the same noise as
+    /// the reference implementation.
+    pub fn new(seed: u64) -> Self {
+        Self { seed: seed as i64 }
+    }
+
+    /// Creates the noise with a seed drawn from the given generator.
+    pub fn from_rng<G: Prng<u64> + ?Sized>(rng: &mut G) -> Self {
+        Self::new(rng.next_val())
+    }
+
+    pub fn noise2(&self, x: f64, y: f64) -> f64 {
+        // Skew onto the simplex lattice.
+        let s = Self::SKEW_2D * (x + y);
+        let (xs, ys) = (x + s, y + s);
+
+        let (xsb, ysb) = (floor(xs), floor(ys));
+        let (xi, yi) = (xs - xsb as f64, ys - ysb as f64);
+        let xsbp = xsb.wrapping_mul(Self::PRIME_X);
+        let ysbp = ysb.wrapping_mul(Self::PRIME_Y);
+
+        // Unskew.
+        let t = (xi + yi) * Self::UNSKEW_2D;
+        let (dx0, dy0) = (xi + t, yi + t);
+        let seed = self.seed;
+
+        let mut value = 0.0;
+        let a0 = Self::RSQUARED_2D - dx0 * dx0 - dy0 * dy0;
+        if a0 > 0.0 {
+            value = (a0 * a0) * (a0 * a0) * grad2(seed, xsbp, ysbp, dx0, dy0);
+        }
+
+        // The far corner, with its falloff derived from the first one.
+        const U: f64 = 1.0 + 2.0 * OpenSimplex2::UNSKEW_2D;
+        let a1 = (2.0 * U * (1.0 / Self::UNSKEW_2D + 2.0)) * t + (-2.0 * U * U + a0);
+        if a1 > 0.0 {
+            let (xp, yp) = (
+                xsbp.wrapping_add(Self::PRIME_X),
+                ysbp.wrapping_add(Self::PRIME_Y),
+            );
+            value += (a1 * a1) * (a1 * a1) * grad2(seed, xp, yp, dx0 - U, dy0 - U);
+        }
+
+        // The middle corner on whichever side of the diagonal we are.
+        let (dx2, dy2, xp, yp) = if dy0 > dx0 {
+            (
+                dx0 - Self::UNSKEW_2D,
+                dy0 - (Self::UNSKEW_2D + 1.0),
+                xsbp,
+                ysbp.wrapping_add(Self::PRIME_Y),
+            )
+        } else {
+            (
+                dx0 - (Self::UNSKEW_2D + 1.0),
+                dy0 - Self::UNSKEW_2D,
+                xsbp.wrapping_add(Self::PRIME_X),
+                ysbp,
+            )
+        };
+        let a2 = Self::RSQUARED_2D - dx2 * dx2 - dy2 * dy2;
+        if a2 > 0.0 {
+            value += (a2 * a2) * (a2 * a2) * grad2(seed, xp, yp, dx2, dy2);
+        }
+
+        clamp(value)
+    }
+
+    pub fn noise3(&self, x: f64, y: f64, z: f64) -> f64 {
+        // Rotate so the main lattice diagonal points along (1, 1, 1).
+        let r = Self::FALLBACK_ROTATE_3D * (x + y + z);
+        let (xr, yr, zr) = (r - x, r - y, r - z);
+
+        let (xrb, yrb, zrb) = (round(xr), round(yr), round(zr));
+        let mut d = [xr - xrb as f64, yr - yrb as f64, zr - zrb as f64];
+
+        // -1 if positive, 1 if negative.
+        let mut sign = d.map(|v| ((-1.0 - v) as i64 | 1) as f64);
+        let mut abs = [sign[0] * -d[0], sign[1] * -d[1], sign[2] * -d[2]];
+
+        let mut p = [
+            xrb.wrapping_mul(Self::PRIME_X),
+            yrb.wrapping_mul(Self::PRIME_Y),
+            zrb.wrapping_mul(Self::PRIME_Z),
+        ];
+        let primes = [Self::PRIME_X, Self::PRIME_Y, Self::PRIME_Z];
+        let mut seed = self.seed;
+
+        // The closest two points on each of the two cubic lattices.
+        let mut value = 0.0;
+        let mut a = (Self::RSQUARED_3D - d[0] * d[0]) - (d[1] * d[1] + d[2] * d[2]);
+        for lattice in 0..2 {
+            if a > 0.0 {
+                value += (a * a) * (a * a) * grad3(seed, p, d);
+            }
+
+            // The neighbour across the face nearest the point.
+            let axis = if abs[0] >= abs[1] && abs[0] >= abs[2] {
+                0
+            } else if abs[1] > abs[0] && abs[1] >= abs[2] {
+                1
+            } else {
+                2
+            };
+            let mut b = a + abs[axis] + abs[axis];
+            if b > 1.0 {
+                b -= 1.0;
+                let mut q = p;
+                q[axis] = q[axis].wrapping_sub((sign[axis] as i64).wrapping_mul(primes[axis]));
+                let mut e = d;
+                e[axis] += sign[axis];
+                value += (b * b) * (b * b) * grad3(seed, q, e);
+            }
+
+            if lattice == 1 {
+                break;
+            }
+
+            // Move to the other lattice, offset by half a cell.
+            abs = abs.map(|v| 0.5 - v);
+            d = [sign[0] * abs[0], sign[1] * abs[1], sign[2] * abs[2]];
+            a += (0.75 - abs[0]) - (abs[1] + abs[2]);
+            for i in 0..3 {
+                p[i] = p[i].wrapping_add((sign[i] as i64 >> 1) & primes[i]);
+            }
+            sign = sign.map(|s| -s);
+            seed ^= Self::SEED_FLIP_3D;
+        }
+
+        clamp(value)
+    }
+
+    pub fn noise4(&self, x: f64, y: f64, z: f64, w: f64) -> f64 {
+        let s = Self::SKEW_4D * (x + y + z + w);
+        let xs = [x + s, y + s, z + s, w + s];
+
+        let base = xs.map(floor);
+        let mut si = [
+            xs[0] - base[0] as f64,
+            xs[1] - base[1] as f64,
+            xs[2] - base[2] as f64,
+            xs[3] - base[3] as f64,
+        ];
+
+        // The lattice copy sure to have a point in this cell's base simplex,
+        // judged from which of the diagonal slices the point is in.
+        let si_sum = (si[0] + si[1]) + (si[2] + si[3]);
+        let starting = (si_sum * 1.25) as i64;
+        let mut seed = self
+            .seed
+            .wrapping_add(starting.wrapping_mul(Self::SEED_OFFSET_4D));
+        let offset = starting as f64 * -Self::LATTICE_STEP_4D;
+        for v in &mut si {
+            *v += offset;
+        }
+        let mut ssi = (si_sum + offset * 4.0) * Self::UNSKEW_4D;
+
+        let primes = [Self::PRIME_X, Self::PRIME_Y, Self::PRIME_Z, Self::PRIME_W];
+        let mut p = [
+            base[0].wrapping_mul(primes[0]),
+            base[1].wrapping_mul(primes[1]),
+            base[2].wrapping_mul(primes[2]),
+            base[3].wrapping_mul(primes[3]),
+        ];
+
+        // One point from each of five copies of the A4 lattice.
+        let mut value = 0.0;
+        for copy in 0..5 {
+            // The closest vertex of the simplex based at the current vertex.
+            let score0 = 1.0 + ssi * (-1.0 / Self::UNSKEW_4D);
+            let step = if si[0] >= si[1] && si[0] >= si[2] && si[0] >= si[3] && si[0] >= score0 {
+                Some(0)
+            } else if si[1] > si[0] && si[1] >= si[2] && si[1] >= si[3] && si[1] >= score0 {
+                Some(1)
+            } else if si[2] > si[0] && si[2] > si[1] && si[2] >= si[3] && si[2] >= score0 {
+                Some(2)
+            } else if si[3] > si[0] && si[3] > si[1] && si[3] > si[2] && si[3] >= score0 {
+                Some(3)
+            } else {
+                None
+            };
+            if let Some(i) = step {
+                p[i] = p[i].wrapping_add(primes[i]);
+                si[i] -= 1.0;
+                ssi -= Self::UNSKEW_4D;
+            }
+
+            let d = si.map(|v| v + ssi);
+            let mut a = (d[0] * d[0] + d[1] * d[1]) + (d[2] * d[2] + d[3] * d[3]);
+            if a < Self::RSQUARED_4D {
+                a -= Self::RSQUARED_4D;
+                a *= a;
+                value += a * a * grad4(seed, p, d);
+            }
+
+            if copy == 4 {
+                break;
+            }
+
+            // Next copy, shifted by -0.2 along every axis.
+            for v in &mut si {
+                *v += Self::LATTICE_STEP_4D;
+            }
+            ssi += Self::LATTICE_STEP_4D * 4.0 * Self::UNSKEW_4D;
+            seed = seed.wrapping_sub(Self::SEED_OFFSET_4D);
+
+            // Copies before the starting one belong to the next cell.
+            if copy == starting {
+                for i in 0..4 {
+                    p[i] = p[i].wrapping_sub(primes[i]);
+                }
+                seed = seed.wrapping_add(Self::SEED_OFFSET_4D.wrapping_mul(5));
+            }
+        }
+
+        clamp(value)
+    }
+}
+
+impl NoiseFn<2> for OpenSimplex2 {
+    fn sample(&self, [x, y]: [f64; 2]) -> f64 {
+        self.noise2(x, y)
+    }
+}
+
+impl NoiseFn<3> for OpenSimplex2 {
+    fn sample(&self, [x, y, z]: [f64; 3]) -> f64 {
+        self.noise3(x, y, z)
+    }
+}
+
+impl NoiseFn<4> for OpenSimplex2 {
+    fn sample(&self, [x, y, z, w]: [f64; 4]) -> f64 {
+        self.noise4(x, y, z, w)
+    }
+}
+
+/// Rounds half away from zero.
+#[inline]
+fn round(v: f64) -> i64 {
+    if v < 0.0 {
+        (v - 0.5) as i64
+    } else {
+        (v + 0.5) as i64
+    }
+}
+
+#[inline]
+fn clamp(v: f64) -> f64 {
+    v.clamp(-1.0, 1.0)
+}
+
+/// Hashes the seed and a lattice point into a gradient index: the top bits
+/// of the product are folded down, then masked to `2^bits` slots of `size`
+/// floats. The reference repeats its table to fill the slots; the modulo
+/// does the same without storing the copies.
+#[inline]
+fn gradient(hash: i64, bits: u32, size: usize, table: &[f64]) -> usize {
+    let hash = hash.wrapping_mul(OpenSimplex2::HASH_MULTIPLIER);
+    let shift = size.trailing_zeros();
+    let hash = hash ^ (hash >> (64 - bits + shift));
+    let index = (hash as i32 & (((1 << bits) - 1) << shift)) as usize;
+    index % table.len()
+}
+
+#[inline]
+fn grad2(seed: i64, xp: i64, yp: i64, dx: f64, dy: f64) -> f64 {
+    let g = gradient(seed ^ xp ^ yp, 7, 2, &GRAD2);
+    GRAD2[g] * dx + GRAD2[g + 1] * dy
+}
+
+#[inline]
+fn grad3(seed: i64, p: [i64; 3], d: [f64; 3]) -> f64 {
+    let g = gradient((seed ^ p[0]) ^ (p[1] ^ p[2]), 8, 4, &GRAD3);
+    GRAD3[g] * d[0] + GRAD3[g + 1] * d[1] + GRAD3[g + 2] * d[2]
+}
+
+#[inline]
+fn grad4(seed: i64, p: [i64; 4], d: [f64; 4]) -> f64 {
+    let g = gradient(seed ^ (p[0] ^ p[1]) ^ (p[2] ^ p[3]), 9, 4, &GRAD4);
+    (GRAD4[g] * d[0] + GRAD4[g + 1] * d[1]) + (GRAD4[g + 2] * d[2] + GRAD4[g + 3] * d[3])
+}
+
+/// Divides a gradient table by its normalizer, so the noise lands in
+/// `[-1, 1]`.
+const fn normalize<const N: usize>(mut table: [f64; N], normalizer: f64) -> [f64; N] {
+    let mut i = 0;
+    while i < N {
+        table[i] /= normalizer;
+        i += 1;
+    }
+    table
+}
+
+// The reference tables: 24 directions in 2D, 48 in 3D (padded to four
+// floats) and 160 in 4D.
+
+#[rustfmt::skip]
+const GRAD2: [f64; 48] = normalize([
+     0.38268343236509,   0.923879532511287,
+     0.923879532511287,  0.38268343236509,
+     0.923879532511287, -0.38268343236509,
+     0.38268343236509,  -0.923879532511287,
+    -0.38268343236509,  -0.923879532511287,
+    -0.923879532511287, -0.38268343236509,
+    -0.923879532511287,  0.38268343236509,
+    -0.38268343236509,   0.923879532511287,
+    //-------------------------------------//
+     0.130526192220052,  0.99144486137381,
+     0.608761429008721,  0.793353340291235,
+     0.793353340291235,  0.608761429008721,
+     0.99144486137381,   0.130526192220051,
+     0.99144486137381,  -0.130526192220051,
+     0.793353340291235, -0.60876142900872,
+     0.608761429008721, -0.793353340291235,
+     0.130526192220052, -0.99144486137381,
+    -0.130526192220052, -0.99144486137381,
+    -0.608761429008721, -0.793353340291235,
+    -0.793353340291235, -0.608761429008721,
+    -0.99144486137381,  -0.130526192220052,
+    -0.99144486137381,   0.130526192220051,
+    -0.793353340291235,  0.608761429008721,
+    -0.608761429008721,  0.793353340291235,
+    -0.130526192220052,  0.99144486137381,
+], 0.01001634121365712);
+
+#[rustfmt::skip]
+const GRAD3: [f64; 192] = normalize([
+     2.22474487139,       2.22474487139,      -1.0,                 0.0,
+     2.22474487139,       2.22474487139,       1.0,                 0.0,
+     3.0862664687972017,  1.1721513422464978,  0.0,                 0.0,
+     1.1721513422464978,  3.0862664687972017,  0.0,                 0.0,
+    -2.22474487139,       2.22474487139,      -1.0,                 0.0,
+    -2.22474487139,       2.22474487139,       1.0,                 0.0,
+    -1.1721513422464978,  3.0862664687972017,  0.0,                 0.0,
+    -3.0862664687972017,  1.1721513422464978,  0.0,                 0.0,
+    -1.0,                -2.22474487139,      -2.22474487139,       0.0,
+     1.0,                -2.22474487139,      -2.22474487139,       0.0,
+     0.0,                -3.0862664687972017, -1.1721513422464978,  0.0,
+     0.0,                -1.1721513422464978, -3.0862664687972017,  0.0,
+    -1.0,                -2.22474487139,       2.22474487139,       0.0,
+     1.0,                -2.22474487139,       2.22474487139,       0.0,
+     0.0,                -1.1721513422464978,  3.0862664687972017,  0.0,
+     0.0,                -3.0862664687972017,  1.1721513422464978,  0.0,
+    //--------------------------------------------------------------------//
+    -2.22474487139,      -2.22474487139,      -1.0,                 0.0,
+    -2.22474487139,      -2.22474487139,       1.0,                 0.0,
+    -3.0862664687972017, -1.1721513422464978,  0.0,                 0.0,
+    -1.1721513422464978, -3.0862664687972017,  0.0,                 0.0,
+    -2.22474487139,      -1.0,                -2.22474487139,       0.0,
+    -2.22474487139,       1.0,                -2.22474487139,       0.0,
+    -1.1721513422464978,  0.0,                -3.0862664687972017,  0.0,
+    -3.0862664687972017,  0.0,                -1.1721513422464978,  0.0,
+    -2.22474487139,      -1.0,                 2.22474487139,       0.0,
+    -2.22474487139,       1.0,                 2.22474487139,       0.0,
+    -3.0862664687972017,  0.0,                 1.1721513422464978,  0.0,
+    -1.1721513422464978,  0.0,                 3.0862664687972017,  0.0,
+    -1.0,                 2.22474487139,      -2.22474487139,       0.0,
+     1.0,                 2.22474487139,      -2.22474487139,       0.0,
+     0.0,                 1.1721513422464978, -3.0862664687972017,  0.0,
+     0.0,                 3.0862664687972017, -1.1721513422464978,  0.0,
+    -1.0,                 2.22474487139,       2.22474487139,       0.0,
+     1.0,                 2.22474487139,       2.22474487139,       0.0,
+     0.0,                 3.0862664687972017,  1.1721513422464978,  0.0,
+     0.0,                 1.1721513422464978,  3.0862664687972017,  0.0,
+     2.22474487139,      -2.22474487139,      -1.0,                 0.0,
+     2.22474487139,      -2.22474487139,       1.0,                 0.0,
+     1.1721513422464978, -3.0862664687972017,  0.0,                 0.0,
+     3.0862664687972017, -1.1721513422464978,  0.0,                 0.0,
+     2.22474487139,      -1.0,                -2.22474487139,       0.0,
+     2.22474487139,       1.0,                -2.22474487139,       0.0,
+     3.0862664687972017,  0.0,                -1.1721513422464978,  0.0,
+     1.1721513422464978,  0.0,                -3.0862664687972017,  0.0,
+     2.22474487139,      -1.0,                 2.22474487139,       0.0,
+     2.22474487139,       1.0,                 2.22474487139,       0.0,
+     1.1721513422464978,  0.0,                 3.0862664687972017,  0.0,
+     3.0862664687972017,  0.0,                 1.1721513422464978,  0.0,
+], 0.07969837668935331);
+
+#[rustfmt::skip]
+const GRAD4: [f64; 640] = normalize([
+    -0.6740059517812944,   -0.3239847771997537,   -0.3239847771997537,    0.5794684678643381,
+    -0.7504883828755602,   -0.4004672082940195,    0.15296486218853164,   0.5029860367700724,
+    -0.7504883828755602,    0.15296486218853164,  -0.4004672082940195,    0.5029860367700724,
+    -0.8828161875373585,    0.08164729285680945,   0.08164729285680945,   0.4553054119602712,
+    -0.4553054119602712,   -0.08164729285680945,  -0.08164729285680945,   0.8828161875373585,
+    -0.5029860367700724,   -0.15296486218853164,   0.4004672082940195,    0.7504883828755602,
+    -0.5029860367700724,    0.4004672082940195,   -0.15296486218853164,   0.7504883828755602,
+    -0.5794684678643381,    0.3239847771997537,    0.3239847771997537,    0.6740059517812944,
+    -0.6740059517812944,   -0.3239847771997537,    0.5794684678643381,   -0.3239847771997537,
+    -0.7504883828755602,   -0.4004672082940195,    0.5029860367700724,    0.15296486218853164,
+    -0.7504883828755602,    0.15296486218853164,   0.5029860367700724,   -0.4004672082940195,
+    -0.8828161875373585,    0.08164729285680945,   0.4553054119602712,    0.08164729285680945,
+    -0.4553054119602712,   -0.08164729285680945,   0.8828161875373585,   -0.08164729285680945,
+    -0.5029860367700724,   -0.15296486218853164,   0.7504883828755602,    0.4004672082940195,
+    -0.5029860367700724,    0.4004672082940195,    0.7504883828755602,   -0.15296486218853164,
+    -0.5794684678643381,    0.3239847771997537,    0.6740059517812944,    0.3239847771997537,
+    -0.6740059517812944,    0.5794684678643381,   -0.3239847771997537,   -0.3239847771997537,
+    -0.7504883828755602,    0.5029860367700724,   -0.4004672082940195,    0.15296486218853164,
+    -0.7504883828755602,    0.5029860367700724,    0.15296486218853164,  -0.4004672082940195,
+    -0.8828161875373585,    0.4553054119602712,    0.08164729285680945,   0.08164729285680945,
+    -0.4553054119602712,    0.8828161875373585,   -0.08164729285680945,  -0.08164729285680945,
+    -0.5029860367700724,    0.7504883828755602,   -0.15296486218853164,   0.4004672082940195,
+    -0.5029860367700724,    0.7504883828755602,    0.4004672082940195,   -0.15296486218853164,
+    -0.5794684678643381,    0.6740059517812944,    0.3239847771997537,    0.3239847771997537,
+     0.5794684678643381,   -0.6740059517812944,   -0.3239847771997537,   -0.3239847771997537,
+     0.5029860367700724,   -0.7504883828755602,   -0.4004672082940195,    0.15296486218853164,
+     0.5029860367700724,   -0.7504883828755602,    0.15296486218853164,  -0.4004672082940195,
+     0.4553054119602712,   -0.8828161875373585,    0.08164729285680945,   0.08164729285680945,
+     0.8828161875373585,   -0.4553054119602712,   -0.08164729285680945,  -0.08164729285680945,
+     0.7504883828755602,   -0.5029860367700724,   -0.15296486218853164,   0.4004672082940195,
+     0.7504883828755602,   -0.5029860367700724,    0.4004672082940195,   -0.15296486218853164,
+     0.6740059517812944,   -0.5794684678643381,    0.3239847771997537,    0.3239847771997537,
+    //------------------------------------------------------------------------------------------//
+    -0.753341017856078,    -0.37968289875261624,  -0.37968289875261624,  -0.37968289875261624,
+    -0.7821684431180708,   -0.4321472685365301,   -0.4321472685365301,    0.12128480194602098,
+    -0.7821684431180708,   -0.4321472685365301,    0.12128480194602098,  -0.4321472685365301,
+    -0.7821684431180708,    0.12128480194602098,  -0.4321472685365301,   -0.4321472685365301,
+    -0.8586508742123365,   -0.508629699630796,     0.044802370851755174,  0.044802370851755174,
+    -0.8586508742123365,    0.044802370851755174, -0.508629699630796,     0.044802370851755174,
+    -0.8586508742123365,    0.044802370851755174,  0.044802370851755174, -0.508629699630796,
+    -0.9982828964265062,   -0.03381941603233842,  -0.03381941603233842,  -0.03381941603233842,
+    -0.37968289875261624,  -0.753341017856078,    -0.37968289875261624,  -0.37968289875261624,
+    -0.4321472685365301,   -0.7821684431180708,   -0.4321472685365301,    0.12128480194602098,
+    -0.4321472685365301,   -0.7821684431180708,    0.12128480194602098,  -0.4321472685365301,
+     0.12128480194602098,  -0.7821684431180708,   -0.4321472685365301,   -0.4321472685365301,
+    -0.508629699630796,    -0.8586508742123365,    0.044802370851755174,  0.044802370851755174,
+     0.044802370851755174, -0.8586508742123365,   -0.508629699630796,     0.044802370851755174,
+     0.044802370851755174, -0.8586508742123365,    0.044802370851755174, -0.508629699630796,
+    -0.03381941603233842,  -0.9982828964265062,   -0.03381941603233842,  -0.03381941603233842,
+    -0.37968289875261624,  -0.37968289875261624,  -0.753341017856078,    -0.37968289875261624,
+    -0.4321472685365301,   -0.4321472685365301,   -0.7821684431180708,    0.12128480194602098,
+    -0.4321472685365301,    0.12128480194602098,  -0.7821684431180708,   -0.4321472685365301,
+     0.12128480194602098,  -0.4321472685365301,   -0.7821684431180708,   -0.4321472685365301,
+    -0.508629699630796,     0.044802370851755174, -0.8586508742123365,    0.044802370851755174,
+     0.044802370851755174, -0.508629699630796,    -0.8586508742123365,    0.044802370851755174,
+     0.044802370851755174,  0.044802370851755174, -0.8586508742123365,   -0.508629699630796,
+    -0.03381941603233842,  -0.03381941603233842,  -0.9982828964265062,   -0.03381941603233842,
+    -0.37968289875261624,  -0.37968289875261624,  -0.37968289875261624,  -0.753341017856078,
+    -0.4321472685365301,   -0.4321472685365301,    0.12128480194602098,  -0.7821684431180708,
+    -0.4321472685365301,    0.12128480194602098,  -0.4321472685365301,   -0.7821684431180708,
+     0.12128480194602098,  -0.4321472685365301,   -0.4321472685365301,   -0.7821684431180708,
+    -0.508629699630796,     0.044802370851755174,  0.044802370851755174, -0.8586508742123365,
+     0.044802370851755174, -0.508629699630796,     0.044802370851755174, -0.8586508742123365,
+     0.044802370851755174,  0.044802370851755174, -0.508629699630796,    -0.8586508742123365,
+    -0.03381941603233842,  -0.03381941603233842,  -0.03381941603233842,  -0.9982828964265062,
+    -0.3239847771997537,   -0.6740059517812944,   -0.3239847771997537,    0.5794684678643381,
+    -0.4004672082940195,   -0.7504883828755602,    0.15296486218853164,   0.5029860367700724,
+     0.15296486218853164,  -0.7504883828755602,   -0.4004672082940195,    0.5029860367700724,
+     0.08164729285680945,  -0.8828161875373585,    0.08164729285680945,   0.4553054119602712,
+    -0.08164729285680945,  -0.4553054119602712,   -0.08164729285680945,   0.8828161875373585,
+    -0.15296486218853164,  -0.5029860367700724,    0.4004672082940195,    0.7504883828755602,
+     0.4004672082940195,   -0.5029860367700724,   -0.15296486218853164,   0.7504883828755602,
+     0.3239847771997537,   -0.5794684678643381,    0.3239847771997537,    0.6740059517812944,
+    -0.3239847771997537,   -0.3239847771997537,   -0.6740059517812944,    0.5794684678643381,
+    -0.4004672082940195,    0.15296486218853164,  -0.7504883828755602,    0.5029860367700724,
+     0.15296486218853164,  -0.4004672082940195,   -0.7504883828755602,    0.5029860367700724,
+     0.08164729285680945,   0.08164729285680945,  -0.8828161875373585,    0.4553054119602712,
+    -0.08164729285680945,  -0.08164729285680945,  -0.4553054119602712,    0.8828161875373585,
+    -0.15296486218853164,   0.4004672082940195,   -0.5029860367700724,    0.7504883828755602,
+     0.4004672082940195,   -0.15296486218853164,  -0.5029860367700724,    0.7504883828755602,
+     0.3239847771997537,    0.3239847771997537,   -0.5794684678643381,    0.6740059517812944,
+    -0.3239847771997537,   -0.6740059517812944,    0.5794684678643381,   -0.3239847771997537,
+    -0.4004672082940195,   -0.7504883828755602,    0.5029860367700724,    0.15296486218853164,
+     0.15296486218853164,  -0.7504883828755602,    0.5029860367700724,   -0.4004672082940195,
+     0.08164729285680945,  -0.8828161875373585,    0.4553054119602712,    0.08164729285680945,
+    -0.08164729285680945,  -0.4553054119602712,    0.8828161875373585,   -0.08164729285680945,
+    -0.15296486218853164,  -0.5029860367700724,    0.7504883828755602,    0.4004672082940195,
+     0.4004672082940195,   -0.5029860367700724,    0.7504883828755602,   -0.15296486218853164,
+     0.3239847771997537,   -0.5794684678643381,    0.6740059517812944,    0.3239847771997537,
+    -0.3239847771997537,   -0.3239847771997537,    0.5794684678643381,   -0.6740059517812944,
+    -0.4004672082940195,    0.15296486218853164,   0.5029860367700724,   -0.7504883828755602,
+     0.15296486218853164,  -0.4004672082940195,    0.5029860367700724,   -0.7504883828755602,
+     0.08164729285680945,   0.08164729285680945,   0.4553054119602712,   -0.8828161875373585,
+    -0.08164729285680945,  -0.08164729285680945,   0.8828161875373585,   -0.4553054119602712,
+    -0.15296486218853164,   0.4004672082940195,    0.7504883828755602,   -0.5029860367700724,
+     0.4004672082940195,   -0.15296486218853164,   0.7504883828755602,   -0.5029860367700724,
+     0.3239847771997537,    0.3239847771997537,    0.6740059517812944,   -0.5794684678643381,
+    -0.3239847771997537,    0.5794684678643381,   -0.6740059517812944,   -0.3239847771997537,
+    -0.4004672082940195,    0.5029860367700724,   -0.7504883828755602,    0.15296486218853164,
+     0.15296486218853164,   0.5029860367700724,   -0.7504883828755602,   -0.4004672082940195,
+     0.08164729285680945,   0.4553054119602712,   -0.8828161875373585,    0.08164729285680945,
+    -0.08164729285680945,   0.8828161875373585,   -0.4553054119602712,   -0.08164729285680945,
+    -0.15296486218853164,   0.7504883828755602,   -0.5029860367700724,    0.4004672082940195,
+     0.4004672082940195,    0.7504883828755602,   -0.5029860367700724,   -0.15296486218853164,
+     0.3239847771997537,    0.6740059517812944,   -0.5794684678643381,    0.3239847771997537,
+    -0.3239847771997537,    0.5794684678643381,   -0.3239847771997537,   -0.6740059517812944,
+    -0.4004672082940195,    0.5029860367700724,    0.15296486218853164,  -0.7504883828755602,
+     0.15296486218853164,   0.5029860367700724,   -0.4004672082940195,   -0.7504883828755602,
+     0.08164729285680945,   0.4553054119602712,    0.08164729285680945,  -0.8828161875373585,
+    -0.08164729285680945,   0.8828161875373585,   -0.08164729285680945,  -0.4553054119602712,
+    -0.15296486218853164,   0.7504883828755602,    0.4004672082940195,   -0.5029860367700724,
+     0.4004672082940195,    0.7504883828755602,   -0.15296486218853164,  -0.5029860367700724,
+     0.3239847771997537,    0.6740059517812944,    0.3239847771997537,   -0.5794684678643381,
+     0.5794684678643381,   -0.3239847771997537,   -0.6740059517812944,   -0.3239847771997537,
+     0.5029860367700724,   -0.4004672082940195,   -0.7504883828755602,    0.15296486218853164,
+     0.5029860367700724,    0.15296486218853164,  -0.7504883828755602,   -0.4004672082940195,
+     0.4553054119602712,    0.08164729285680945,  -0.8828161875373585,    0.08164729285680945,
+     0.8828161875373585,   -0.08164729285680945,  -0.4553054119602712,   -0.08164729285680945,
+     0.7504883828755602,   -0.15296486218853164,  -0.5029860367700724,    0.4004672082940195,
+     0.7504883828755602,    0.4004672082940195,   -0.5029860367700724,   -0.15296486218853164,
+     0.6740059517812944,    0.3239847771997537,   -0.5794684678643381,    0.3239847771997537,
+     0.5794684678643381,   -0.3239847771997537,   -0.3239847771997537,   -0.6740059517812944,
+     0.5029860367700724,   -0.4004672082940195,    0.15296486218853164,  -0.7504883828755602,
+     0.5029860367700724,    0.15296486218853164,  -0.4004672082940195,   -0.7504883828755602,
+     0.4553054119602712,    0.08164729285680945,   0.08164729285680945,  -0.8828161875373585,
+     0.8828161875373585,   -0.08164729285680945,  -0.08164729285680945,  -0.4553054119602712,
+     0.7504883828755602,   -0.15296486218853164,   0.4004672082940195,   -0.5029860367700724,
+     0.7504883828755602,    0.4004672082940195,   -0.15296486218853164,  -0.5029860367700724,
+     0.6740059517812944,    0.3239847771997537,    0.3239847771997537,   -0.5794684678643381,
+     0.03381941603233842,   0.03381941603233842,   0.03381941603233842,   0.9982828964265062,
+    -0.044802370851755174, -0.044802370851755174,  0.508629699630796,     0.8586508742123365,
+    -0.044802370851755174,  0.508629699630796,    -0.044802370851755174,  0.8586508742123365,
+    -0.12128480194602098,   0.4321472685365301,    0.4321472685365301,    0.7821684431180708,
+     0.508629699630796,    -0.044802370851755174, -0.044802370851755174,  0.8586508742123365,
+     0.4321472685365301,   -0.12128480194602098,   0.4321472685365301,    0.7821684431180708,
+     0.4321472685365301,    0.4321472685365301,   -0.12128480194602098,   0.7821684431180708,
+     0.37968289875261624,   0.37968289875261624,   0.37968289875261624,   0.753341017856078,
+     0.03381941603233842,   0.03381941603233842,   0.9982828964265062,    0.03381941603233842,
+    -0.044802370851755174,  0.044802370851755174,  0.8586508742123365,    0.508629699630796,
+    -0.044802370851755174,  0.508629699630796,     0.8586508742123365,   -0.044802370851755174,
+    -0.12128480194602098,   0.4321472685365301,    0.7821684431180708,    0.4321472685365301,
+     0.508629699630796,    -0.044802370851755174,  0.8586508742123365,   -0.044802370851755174,
+     0.4321472685365301,   -0.12128480194602098,   0.7821684431180708,    0.4321472685365301,
+     0.4321472685365301,    0.4321472685365301,    0.7821684431180708,   -0.12128480194602098,
+     0.37968289875261624,   0.37968289875261624,   0.753341017856078,     0.37968289875261624,
+     0.03381941603233842,   0.9982828964265062,    0.03381941603233842,   0.03381941603233842,
+    -0.044802370851755174,  0.8586508742123365,   -0.044802370851755174,  0.508629699630796,
+    -0.044802370851755174,  0.8586508742123365,    0.508629699630796,    -0.044802370851755174,
+    -0.12128480194602098,   0.7821684431180708,    0.4321472685365301,    0.4321472685365301,
+     0.508629699630796,     0.8586508742123365,   -0.044802370851755174, -0.044802370851755174,
+     0.4321472685365301,    0.7821684431180708,   -0.12128480194602098,   0.4321472685365301,
+     0.4321472685365301,    0.7821684431180708,    0.4321472685365301,   -0.12128480194602098,
+     0.37968289875261624,   0.753341017856078,     0.37968289875261624,   0.37968289875261624,
+     0.9982828964265062,    0.03381941603233842,   0.03381941603233842,   0.03381941603233842,
+     0.8586508742123365,   -0.044802370851755174, -0.044802370851755174,  0.508629699630796,
+     0.8586508742123365,   -0.044802370851755174,  0.508629699630796,    -0.044802370851755174,
+     0.7821684431180708,   -0.12128480194602098,   0.4321472685365301,    0.4321472685365301,
+     0.8586508742123365,    0.508629699630796,    -0.044802370851755174, -0.044802370851755174,
+     0.7821684431180708,    0.4321472685365301,   -0.12128480194602098,   0.4321472685365301,
+     0.7821684431180708,    0.4321472685365301,    0.4321472685365301,   -0.12128480194602098,
+     0.753341017856078,     0.37968289875261624,   0.37968289875261624,   0.37968289875261624,
+], 0.0220065933241897);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bounded() {
+        let n = OpenSimplex2::new(12345);
+        for i in 0..2000 {
+            let t = i as f64 * 0.137;
+            let v = [
+                n.noise2(t, t * 0.71),
+                n.noise3(t, t * 0.71, t * 1.31),
+                n.noise4(t, t * 0.71, t * 1.31, t * 0.53),
+            ];
+            assert!(v.iter().all(|v| (-1.0..=1.0).contains(v)));
+        }
+    }
+
+    #[test]
+    fn dump_values() {
+        let n = OpenSimplex2::new(123456);
+        assert_eq!(n.noise2(0.3, 1.7), 0.15916821798008854);
+        assert_eq!(n.noise3(0.3, 1.7, -2.2), -0.22472257893028497);
+        assert_eq!(n.noise4(0.3, 1.7, -2.2, 9.9), -0.39522714445599794);
+
+        // The reference's f32 results for the same seed and points.
+        assert!((n.noise2(0.3, 1.7) - 0.15916826).abs() < 1e-6);
+        assert!((n.noise3(0.3, 1.7, -2.2) - -0.22472264).abs() < 1e-6);
+        assert!((n.noise4(0.3, 1.7, -2.2, 9.9) - -0.3952269).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,287 @@
+use super::{floor, NoiseFn, Permutation};
+use crate::Prng;
+
+/// Ken Perlin's improved gradient noise in one to four dimensions.
+///
+/// Output is in `[-1, 1]` and is zero on every integer lattice point.
+///
+/// # Examples
+///
+/// ```rust
+/// use tc_prng::{noise::Perlin, split_mix};
+///
+/// let perlin = Perlin::from_rng(&mut split_mix(42_u64));
+/// let v = perlin.noise2(0.5, 1.25);
+/// assert!((-1.0..=1.0).contains(&v));
+/// ```
+#[derive(Clone)]
+pub struct Perlin {
+    perm: Permutation,
+}
+
+impl Perlin {
+    const SCALE1: f64 = 2.0;
+    const SCALE2: f64 = 1.0;
+    const SCALE3: f64 = 0.9649;
+    const SCALE4: f64 = 0.8613;
+
+    /// Creates the noise with a table shuffled by `SplitMix64::new(seed)`.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            perm: Permutation::new(seed),
+        }
+    }
+
+    /// Creates the noise with a table shuffled by the given generator.
+    pub fn from_rng<G: Prng<u32> + ?Sized>(rng: &mut G) -> Self {
+        Self {
+            perm: Permutation::from_rng(rng),
+        }
+    }
+
+    pub fn noise1(&self, x: f64) -> f64 {
+        let xf = floor(x);
+        let x = x - xf as f64;
+        let p = &self.perm;
+
+        let a = grad1(p.hash(xf), x);
+        let b = grad1(p.hash(xf + 1), x - 1.0);
+        clamp(lerp(fade(x), a, b) * Self::SCALE1)
+    }
+
+    pub fn noise2(&self, x: f64, y: f64) -> f64 {
+        let (xf, yf) = (floor(x), floor(y));
+        let (x, y) = (x - xf as f64, y - yf as f64);
+        let (u, v) = (fade(x), fade(y));
+        let p = &self.perm;
+
+        let a = p.hash(xf) + (yf & 255) as usize;
+        let b = p.hash(xf + 1) + (yf & 255) as usize;
+
+        let n = lerp(
+            v,
+            lerp(u, grad2(p.get(a), x, y), grad2(p.get(b), x - 1.0, y)),
+            lerp(
+                u,
+                grad2(p.get(a + 1), x, y - 1.0),
+                grad2(p.get(b + 1), x - 1.0, y - 1.0),
+            ),
+        );
+        clamp(n * Self::SCALE2)
+    }
+
+    pub fn noise3(&self, x: f64, y: f64, z: f64) -> f64 {
+        let (xf, yf, zf) = (floor(x), floor(y), floor(z));
+        let (x, y, z) = (x - xf as f64, y - yf as f64, z - zf as f64);
+        let (u, v, w) = (fade(x), fade(y), fade(z));
+        let p = &self.perm;
+
+        let (yi, zi) = ((yf & 255) as usize, (zf & 255) as usize);
+        let a = p.hash(xf) + yi;
+        let aa = p.get(a) + zi;
+        let ab = p.get(a + 1) + zi;
+        let b = p.hash(xf + 1) + yi;
+        let ba = p.get(b) + zi;
+        let bb = p.get(b + 1) + zi;
+
+        let n = lerp(
+            w,
+            lerp(
+                v,
+                lerp(
+                    u,
+                    grad3(p.get(aa), x, y, z),
+                    grad3(p.get(ba), x - 1.0, y, z),
+                ),
+                lerp(
+                    u,
+                    grad3(p.get(ab), x, y - 1.0, z),
+                    grad3(p.get(bb), x - 1.0, y - 1.0, z),
+                ),
+            ),
+            lerp(
+                v,
+                lerp(
+                    u,
+                    grad3(p.get(aa + 1), x, y, z - 1.0),
+                    grad3(p.get(ba + 1), x - 1.0, y, z - 1.0),
+                ),
+                lerp(
+                    u,
+                    grad3(p.get(ab + 1), x, y - 1.0, z - 1.0),
+                    grad3(p.get(bb + 1), x - 1.0, y - 1.0, z - 1.0),
+                ),
+            ),
+        );
+        clamp(n * Self::SCALE3)
+    }
+
+    pub fn noise4(&self, x: f64, y: f64, z: f64, w: f64) -> f64 {
+        let cell = [floor(x), floor(y), floor(z), floor(w)];
+        let frac = [
+            x - cell[0] as f64,
+            y - cell[1] as f64,
+            z - cell[2] as f64,
+            w - cell[3] as f64,
+        ];
+        let p = &self.perm;
+
+        // Gradient contributions of the 16 corners, indexed by corner bits
+        // (bit 0 = x, bit 1 = y, bit 2 = z, bit 3 = w).
+        let mut corners = [0.0; 16];
+        for (c, n) in corners.iter_mut().enumerate() {
+            let mut h = 0;
+            let mut d = [0.0; 4];
+            for axis in 0..4 {
+                let o = ((c >> axis) & 1) as i64;
+                h = p.get(h + ((cell[axis] + o) & 255) as usize);
+                d[axis] = frac[axis] - o as f64;
+            }
+            *n = grad4(h, d[0], d[1], d[2], d[3]);
+        }
+
+        let mut len = 16;
+        for f in frac {
+            let t = fade(f);
+            len /= 2;
+            for c in 0..len {
+                // Pairs differ in the lowest remaining axis bit.
+                corners[c] = lerp(t, corners[c * 2], corners[c * 2 + 1]);
+            }
+        }
+        clamp(corners[0] * Self::SCALE4)
+    }
+}
+
+impl NoiseFn<1> for Perlin {
+    fn sample(&self, [x]: [f64; 1]) -> f64 {
+        self.noise1(x)
+    }
+}
+
+impl NoiseFn<2> for Perlin {
+    fn sample(&self, [x, y]: [f64; 2]) -> f64 {
+        self.noise2(x, y)
+    }
+}
+
+impl NoiseFn<3> for Perlin {
+    fn sample(&self, [x, y, z]: [f64; 3]) -> f64 {
+        self.noise3(x, y, z)
+    }
+}
+
+impl NoiseFn<4> for Perlin {
+    fn sample(&self, [x, y, z, w]: [f64; 4]) -> f64 {
+        self.noise4(x, y, z, w)
+    }
+}
+
+#[inline]
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+#[inline]
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    a + t * (b - a)
+}
+
+#[inline]
+fn clamp(v: f64) -> f64 {
+    v.clamp(-1.0, 1.0)
+}
+
+/// One of 16 evenly spaced slopes in `[-1, 1]`.
+#[inline]
+fn grad1(hash: usize, x: f64) -> f64 {
+    let g = (hash & 15) as f64 * (2.0 / 15.0) - 1.0;
+    g * x
+}
+
+#[inline]
+fn grad2(hash: usize, x: f64, y: f64) -> f64 {
+    match hash & 7 {
+        0 => x + y,
+        1 => -x + y,
+        2 => x - y,
+        3 => -x - y,
+        4 => x,
+        5 => -x,
+        6 => y,
+        _ => -y,
+    }
+}
+
+#[inline]
+fn grad3(hash: usize, x: f64, y: f64, z: f64) -> f64 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+    let u = if h & 1 == 0 { u } else { -u };
+    let v = if h & 2 == 0 { v } else { -v };
+    u + v
+}
+
+/// One of the 32 edge midpoints of the 4D hypercube.
+#[inline]
+fn grad4(hash: usize, x: f64, y: f64, z: f64, w: f64) -> f64 {
+    let h = hash & 31;
+    let (a, b, c) = match h >> 3 {
+        0 => (y, z, w),
+        1 => (x, z, w),
+        2 => (x, y, w),
+        _ => (x, y, z),
+    };
+    let a = if h & 1 == 0 { a } else { -a };
+    let b = if h & 2 == 0 { b } else { -b };
+    let c = if h & 4 == 0 { c } else { -c };
+    a + b + c
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn zero_on_lattice() {
+        let n = Perlin::new(3);
+        for i in -4..4 {
+            let i = i as f64;
+            assert_eq!(n.noise1(i), 0.0);
+            assert_eq!(n.noise2(i, -i), 0.0);
+            assert_eq!(n.noise3(i, 2.0, -i), 0.0);
+            assert_eq!(n.noise4(i, 2.0, -i, 5.0), 0.0);
+        }
+    }
+
+    #[test]
+    fn bounded() {
+        let n = Perlin::new(12345);
+        for i in 0..2000 {
+            let t = i as f64 * 0.137;
+            let v = [
+                n.noise1(t),
+                n.noise2(t, t * 0.71),
+                n.noise3(t, t * 0.71, t * 1.31),
+                n.noise4(t, t * 0.71, t * 1.31, t * 0.53),
+            ];
+            assert!(v.iter().all(|v| (-1.0..=1.0).contains(v)));
+        }
+    }
+
+    #[test]
+    fn dump_values() {
+        let n = Perlin::new(123456);
+        assert_eq!(n.noise1(0.3), -0.6635104000000001);
+        assert_eq!(n.noise2(0.3, 1.7), 0.25064091360000007);
+        assert_eq!(n.noise3(0.3, 1.7, -2.2), 0.15472093561374275);
+        assert_eq!(n.noise4(0.3, 1.7, -2.2, 9.9), 0.4475771914217342);
+    }
+}
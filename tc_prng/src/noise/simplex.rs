@@ -0,0 +1,281 @@
+use super::{floor, NoiseFn, Permutation};
+use crate::Prng;
+
+/// Simplex gradient noise in one to four dimensions.
+///
+/// Follows Stefan Gustavson's reference formulation: fewer lattice
+/// contributions per sample than [`Perlin`](super::Perlin) and no visible
+/// axis-aligned artifacts. Output is in `[-1, 1]`.
+#[derive(Clone)]
+pub struct Simplex {
+    perm: Permutation,
+}
+
+impl Simplex {
+    // Skew and unskew factors, (sqrt(n + 1) - 1) / n and
+    // (n + 1 - sqrt(n + 1)) / (n * (n + 1)), written out so no platform
+    // square root is involved.
+    const F2: f64 = 0.366_025_403_784_438_6;
+    const G2: f64 = 0.211_324_865_405_187_13;
+    const F3: f64 = 1.0 / 3.0;
+    const G3: f64 = 1.0 / 6.0;
+    const F4: f64 = 0.309_016_994_374_947_45;
+    const G4: f64 = 0.138_196_601_125_010_5;
+
+    const SCALE1: f64 = 0.395;
+    const SCALE2: f64 = 45.23;
+    const SCALE3: f64 = 32.0;
+    const SCALE4: f64 = 27.0;
+
+    /// Creates the noise with a table shuffled by `SplitMix64::new(seed)`.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            perm: Permutation::new(seed),
+        }
+    }
+
+    /// Creates the noise with a table shuffled by the given generator.
+    pub fn from_rng<G: Prng<u32> + ?Sized>(rng: &mut G) -> Self {
+        Self {
+            perm: Permutation::from_rng(rng),
+        }
+    }
+
+    pub fn noise1(&self, x: f64) -> f64 {
+        let i0 = floor(x);
+        let x0 = x - i0 as f64;
+        let x1 = x0 - 1.0;
+        let p = &self.perm;
+
+        let n0 = corner(1.0 - x0 * x0, grad1(p.hash(i0), x0));
+        let n1 = corner(1.0 - x1 * x1, grad1(p.hash(i0 + 1), x1));
+        clamp((n0 + n1) * Self::SCALE1)
+    }
+
+    pub fn noise2(&self, x: f64, y: f64) -> f64 {
+        let s = (x + y) * Self::F2;
+        let (i, j) = (floor(x + s), floor(y + s));
+        let t = (i + j) as f64 * Self::G2;
+        let x0 = x - (i as f64 - t);
+        let y0 = y - (j as f64 - t);
+
+        let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+
+        let x1 = x0 - i1 as f64 + Self::G2;
+        let y1 = y0 - j1 as f64 + Self::G2;
+        let x2 = x0 - 1.0 + 2.0 * Self::G2;
+        let y2 = y0 - 1.0 + 2.0 * Self::G2;
+
+        let p = &self.perm;
+        let (ii, jj) = ((i & 255) as usize, (j & 255) as usize);
+        let g0 = p.get(ii + p.get(jj));
+        let g1 = p.get(ii + i1 + p.get(jj + j1));
+        let g2 = p.get(ii + 1 + p.get(jj + 1));
+
+        let n0 = corner(0.5 - x0 * x0 - y0 * y0, grad2(g0, x0, y0));
+        let n1 = corner(0.5 - x1 * x1 - y1 * y1, grad2(g1, x1, y1));
+        let n2 = corner(0.5 - x2 * x2 - y2 * y2, grad2(g2, x2, y2));
+        clamp((n0 + n1 + n2) * Self::SCALE2)
+    }
+
+    pub fn noise3(&self, x: f64, y: f64, z: f64) -> f64 {
+        let s = (x + y + z) * Self::F3;
+        let (i, j, k) = (floor(x + s), floor(y + s), floor(z + s));
+        let t = (i + j + k) as f64 * Self::G3;
+        let x0 = x - (i as f64 - t);
+        let y0 = y - (j as f64 - t);
+        let z0 = z - (k as f64 - t);
+
+        let (o1, o2) = if x0 >= y0 {
+            if y0 >= z0 {
+                ([1, 0, 0], [1, 1, 0])
+            } else if x0 >= z0 {
+                ([1, 0, 0], [1, 0, 1])
+            } else {
+                ([0, 0, 1], [1, 0, 1])
+            }
+        } else if y0 < z0 {
+            ([0, 0, 1], [0, 1, 1])
+        } else if x0 < z0 {
+            ([0, 1, 0], [0, 1, 1])
+        } else {
+            ([0, 1, 0], [1, 1, 0])
+        };
+
+        let offsets = [[0, 0, 0], o1, o2, [1, 1, 1]];
+        let p = &self.perm;
+        let (ii, jj, kk) = ((i & 255) as usize, (j & 255) as usize, (k & 255) as usize);
+
+        let mut n = 0.0;
+        for (c, o) in offsets.iter().enumerate() {
+            let g = c as f64 * Self::G3;
+            let xc = x0 - o[0] as f64 + g;
+            let yc = y0 - o[1] as f64 + g;
+            let zc = z0 - o[2] as f64 + g;
+            let h = p.get(ii + o[0] + p.get(jj + o[1] + p.get(kk + o[2])));
+            n += corner(0.6 - xc * xc - yc * yc - zc * zc, grad3(h, xc, yc, zc));
+        }
+        clamp(n * Self::SCALE3)
+    }
+
+    pub fn noise4(&self, x: f64, y: f64, z: f64, w: f64) -> f64 {
+        let s = (x + y + z + w) * Self::F4;
+        let cell = [floor(x + s), floor(y + s), floor(z + s), floor(w + s)];
+        let t = (cell[0] + cell[1] + cell[2] + cell[3]) as f64 * Self::G4;
+        let d0 = [
+            x - (cell[0] as f64 - t),
+            y - (cell[1] as f64 - t),
+            z - (cell[2] as f64 - t),
+            w - (cell[3] as f64 - t),
+        ];
+
+        // Rank each axis by magnitude to find which simplex we are in.
+        let mut rank = [0; 4];
+        for a in 0..4 {
+            for b in (a + 1)..4 {
+                if d0[a] > d0[b] {
+                    rank[a] += 1;
+                } else {
+                    rank[b] += 1;
+                }
+            }
+        }
+
+        let p = &self.perm;
+        let base = cell.map(|c| (c & 255) as usize);
+
+        let mut n = 0.0;
+        for c in 0..5 {
+            // Corner c steps along every axis ranked at least 4 - c.
+            let o = rank.map(|r| usize::from(c > 0 && r >= 4 - c));
+            let g = c as f64 * Self::G4;
+            let d = [
+                d0[0] - o[0] as f64 + g,
+                d0[1] - o[1] as f64 + g,
+                d0[2] - o[2] as f64 + g,
+                d0[3] - o[3] as f64 + g,
+            ];
+            let h = (0..4).rev().fold(0, |h, a| p.get(base[a] + o[a] + h));
+            let r = 0.6 - d[0] * d[0] - d[1] * d[1] - d[2] * d[2] - d[3] * d[3];
+            n += corner(r, grad4(h, d[0], d[1], d[2], d[3]));
+        }
+        clamp(n * Self::SCALE4)
+    }
+}
+
+impl NoiseFn<1> for Simplex {
+    fn sample(&self, [x]: [f64; 1]) -> f64 {
+        self.noise1(x)
+    }
+}
+
+impl NoiseFn<2> for Simplex {
+    fn sample(&self, [x, y]: [f64; 2]) -> f64 {
+        self.noise2(x, y)
+    }
+}
+
+impl NoiseFn<3> for Simplex {
+    fn sample(&self, [x, y, z]: [f64; 3]) -> f64 {
+        self.noise3(x, y, z)
+    }
+}
+
+impl NoiseFn<4> for Simplex {
+    fn sample(&self, [x, y, z, w]: [f64; 4]) -> f64 {
+        self.noise4(x, y, z, w)
+    }
+}
+
+/// Radial falloff `t^4 * g` of a single corner, zero outside its radius.
+#[inline]
+fn corner(t: f64, g: f64) -> f64 {
+    if t < 0.0 {
+        0.0
+    } else {
+        let t2 = t * t;
+        t2 * t2 * g
+    }
+}
+
+#[inline]
+fn clamp(v: f64) -> f64 {
+    v.clamp(-1.0, 1.0)
+}
+
+#[inline]
+fn grad1(hash: usize, x: f64) -> f64 {
+    let h = hash & 15;
+    let g = 1.0 + (h & 7) as f64;
+    if h & 8 == 0 {
+        g * x
+    } else {
+        -g * x
+    }
+}
+
+#[inline]
+fn grad2(hash: usize, x: f64, y: f64) -> f64 {
+    let h = hash & 7;
+    let (u, v) = if h < 4 { (x, y) } else { (y, x) };
+    let u = if h & 1 == 0 { u } else { -u };
+    let v = if h & 2 == 0 { 2.0 * v } else { -2.0 * v };
+    u + v
+}
+
+#[inline]
+fn grad3(hash: usize, x: f64, y: f64, z: f64) -> f64 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+    let u = if h & 1 == 0 { u } else { -u };
+    let v = if h & 2 == 0 { v } else { -v };
+    u + v
+}
+
+#[inline]
+fn grad4(hash: usize, x: f64, y: f64, z: f64, w: f64) -> f64 {
+    let h = hash & 31;
+    let u = if h < 24 { x } else { y };
+    let v = if h < 16 { y } else { z };
+    let w = if h < 8 { z } else { w };
+    let u = if h & 1 == 0 { u } else { -u };
+    let v = if h & 2 == 0 { v } else { -v };
+    let w = if h & 4 == 0 { w } else { -w };
+    u + v + w
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bounded() {
+        let n = Simplex::new(12345);
+        for i in 0..2000 {
+            let t = i as f64 * 0.137;
+            let v = [
+                n.noise1(t),
+                n.noise2(t, t * 0.71),
+                n.noise3(t, t * 0.71, t * 1.31),
+                n.noise4(t, t * 0.71, t * 1.31, t * 0.53),
+            ];
+            assert!(v.iter().all(|v| (-1.0..=1.0).contains(v)));
+        }
+    }
+
+    #[test]
+    fn dump_values() {
+        let n = Simplex::new(123456);
+        assert_eq!(n.noise1(0.3), 0.31216890369);
+        assert_eq!(n.noise2(0.3, 1.7), 0.009247883015365619);
+        assert_eq!(n.noise3(0.3, 1.7, -2.2), 0.025871875687242453);
+        assert_eq!(n.noise4(0.3, 1.7, -2.2, 9.9), 0.295144936956549);
+    }
+}
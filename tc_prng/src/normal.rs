@@ -0,0 +1,127 @@
+//! Normally distributed values.
+//!
+//! [`Normal`] uses Marsaglia's polar method. Each attempt draws two values
+//! from `-1.0..1.0` with [`SampleRange`] and is retried while the point lies
+//! outside the unit circle, about 21% of the time. Only the first of the two
+//! normal values an attempt yields is returned, so every sample depends on
+//! the generator alone and not on earlier calls.
+//!
+//! The method needs `ln`, which the platform's math library computes, so
+//! the last bit of a value may differ between platforms.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tc_prng::{normal::Normal, prelude::*};
+//!
+//! let mut rng = xorshiro(3_u64);
+//! let height = Normal::new(170.0, 8.0).sample(&mut rng);
+//! let noise = Normal::STANDARD.sample(&mut rng);
+//! # let _ = (height, noise);
+//! ```
+
+use crate::{Prng, SampleRange};
+
+/// The normal distribution with a given mean and standard deviation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Normal {
+    mean: f64,
+    std_dev: f64,
+}
+
+impl Normal {
+    /// Mean 0, standard deviation 1.
+    pub const STANDARD: Normal = Normal {
+        mean: 0.0,
+        std_dev: 1.0,
+    };
+
+    /// # Panics
+    ///
+    /// If `std_dev` is negative or either argument is not finite.
+    pub fn new(mean: f64, std_dev: f64) -> Self {
+        assert!(
+            mean.is_finite() && std_dev.is_finite() && std_dev >= 0.0,
+            "invalid normal distribution"
+        );
+        Self { mean, std_dev }
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    pub fn std_dev(&self) -> f64 {
+        self.std_dev
+    }
+
+    pub fn sample<G: Prng<u64> + ?Sized>(&self, rng: &mut G) -> f64 {
+        loop {
+            let u: f64 = (-1.0..1.0).sample(rng);
+            let v: f64 = (-1.0..1.0).sample(rng);
+            let s = u * u + v * v;
+            if s > 0.0 && s < 1.0 {
+                return self.mean + self.std_dev * u * (-2.0 * s.ln() / s).sqrt();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{mock::ScriptedRng, prelude::*, quality};
+
+    /// Standard normal cumulative distribution function.
+    fn phi(x: f64) -> f64 {
+        let tail = quality::special::normal_two_sided(x) / 2.0;
+        if x >= 0.0 {
+            1.0 - tail
+        } else {
+            tail
+        }
+    }
+
+    #[test]
+    fn standard_normal_shape() {
+        let mut rng = xorshiro(9_u64);
+        let samples: Vec<f64> = (0..20_000)
+            .map(|_| Normal::STANDARD.sample(&mut rng))
+            .collect();
+        let result = quality::ks_test("normal", &samples, phi);
+        assert!(result.passes(0.001), "{result}");
+
+        let shifted = Normal::new(10.0, 2.0);
+        let samples: Vec<f64> = (0..20_000).map(|_| shifted.sample(&mut rng)).collect();
+        let result = quality::ks_test("normal", &samples, |x| phi((x - 10.0) / 2.0));
+        assert!(result.passes(0.001), "{result}");
+    }
+
+    #[test]
+    fn rejects_points_outside_circle() {
+        // The first pair is just below (1, 1), outside the circle; the
+        // second is (0, 0.5).
+        let mut rng = ScriptedRng::new(vec![u64::MAX - 1, u64::MAX - 1, 1 << 63, 3 << 62]);
+        let z = Normal::STANDARD.sample(&mut rng);
+        assert_eq!(rng.remaining(), 0);
+        assert!(z.abs() < 1e-9, "{z}");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid normal distribution")]
+    fn negative_std_dev() {
+        Normal::new(0.0, -1.0);
+    }
+
+    #[test]
+    fn dump_value_types() {
+        let mut rng = split_mix(123456_u64);
+
+        // `ln` may differ in the last bit between platforms.
+        let a = Normal::STANDARD.sample(&mut rng);
+        assert!((a - -0.8377690436471729).abs() < 1e-12, "{a}");
+
+        let b = Normal::new(5.0, 0.5).sample(&mut rng);
+        assert!((b - 5.318618544626556).abs() < 1e-12, "{b}");
+    }
+}
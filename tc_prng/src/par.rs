@@ -0,0 +1,124 @@
+//! Deterministic parallel filling of large buffers.
+//!
+//! The buffer is cut into chunks of [`CHUNK_LEN`] elements and chunk `i` is
+//! filled from [`xorshiro_at(seed, i)`](crate::positional::xorshiro_at).
+//! Neither the chunk boundaries nor the generators depend on how many
+//! threads do the work, so the result is identical for any thread count,
+//! including one.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tc_prng::par::{par_fill, par_fill_with};
+//!
+//! let mut a = vec![0.0_f32; 100_000];
+//! let mut b = vec![0.0_f32; 100_000];
+//! par_fill(&mut a, 7, 4);
+//! par_fill(&mut b, 7, 1);
+//! assert_eq!(a, b);
+//!
+//! // Any value built from the generator, e.g. a sum of uniforms.
+//! par_fill_with(&mut a, 7, 0, |rng| (0..4).map(|_| rng.next::<f32>()).sum());
+//! ```
+
+use std::{num::NonZeroUsize, thread};
+
+use crate::{positional::xorshiro_at, xorshiro::Xorshiro64, FromWord};
+
+/// Elements per chunk. Part of the output contract: changing it changes the
+/// values produced for a given seed.
+pub const CHUNK_LEN: usize = 1 << 16;
+
+/// Fills `dst` with values drawn from per-chunk generators derived from
+/// `seed`, on up to `threads` threads (0 uses the available parallelism).
+pub fn par_fill<T>(dst: &mut [T], seed: u64, threads: usize)
+where
+    T: FromWord<u64> + Send,
+{
+    par_fill_with(dst, seed, threads, |rng| rng.next());
+}
+
+/// Like [`par_fill`], building each element with `f`.
+///
+/// `f` is called once per element, in order within a chunk, with that
+/// chunk's generator.
+pub fn par_fill_with<T, F>(dst: &mut [T], seed: u64, threads: usize, f: F)
+where
+    T: Send,
+    F: Fn(&mut Xorshiro64) -> T + Sync,
+{
+    let threads = match threads {
+        0 => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        n => n,
+    };
+    let mut chunks: Vec<_> = dst.chunks_mut(CHUNK_LEN).enumerate().collect();
+    let per_thread = chunks.len().div_ceil(threads).max(1);
+
+    let fill = |work: &mut [(usize, &mut [T])]| {
+        for (i, chunk) in work {
+            let mut rng = xorshiro_at(seed, *i as u64);
+            for v in chunk.iter_mut() {
+                *v = f(&mut rng);
+            }
+        }
+    };
+
+    if threads == 1 || chunks.len() <= 1 {
+        fill(&mut chunks);
+        return;
+    }
+    thread::scope(|s| {
+        for work in chunks.chunks_mut(per_thread) {
+            s.spawn(|| fill(work));
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn independent_of_thread_count() {
+        let len = CHUNK_LEN * 5 + 123;
+        let mut one = vec![0_u64; len];
+        par_fill(&mut one, 99, 1);
+        for threads in [2, 3, 8, 64] {
+            let mut many = vec![0_u64; len];
+            par_fill(&mut many, 99, threads);
+            assert!(one == many, "differs with {threads} threads");
+        }
+    }
+
+    #[test]
+    fn chunks_use_positional_generators() {
+        let mut v = vec![0_u32; CHUNK_LEN + 2];
+        par_fill(&mut v, 5, 2);
+
+        let mut first = xorshiro_at(5, 0_u64);
+        let mut second = xorshiro_at(5, 1_u64);
+        assert_eq!(v[0], first.next::<u32>());
+        assert_eq!(v[1], first.next::<u32>());
+        assert_eq!(v[CHUNK_LEN], second.next::<u32>());
+        assert_eq!(v[CHUNK_LEN + 1], second.next::<u32>());
+    }
+
+    #[test]
+    fn closure_variant() {
+        let mut a = vec![(0_u8, 0.0_f64); 1000];
+        let mut b = a.clone();
+        par_fill_with(&mut a, 3, 4, |rng| (rng.next(), rng.next()));
+        par_fill_with(&mut b, 3, 0, |rng| (rng.next(), rng.next()));
+        assert_eq!(a, b);
+        assert_ne!(a[0], a[1]);
+    }
+
+    #[test]
+    fn empty_and_tiny_buffers() {
+        let mut empty: [u8; 0] = [];
+        par_fill(&mut empty, 1, 4);
+        let mut one = [0_u16; 1];
+        par_fill(&mut one, 1, 4);
+        assert_eq!(one[0], xorshiro_at(1, 0_u64).next::<u16>());
+    }
+}
@@ -0,0 +1,239 @@
+use crate::{
+    state::{Algorithm, Codec, Persist, Reader, StateError},
+    Prng, PrngCore, Seedable,
+};
+
+/// Philox4x32-10 counter-based generator (Salmon et al., Random123).
+///
+/// Every 128-bit counter maps to an independent block of four words through
+/// a keyed bijection, so any position of the stream can be computed in O(1)
+/// with [`at`](Self::at) or jumped to with [`seek`](Self::seek). Used
+/// sequentially it walks the counters from zero and hands out the block
+/// words in order.
+///
+/// # Examples
+///
+/// ```rust
+/// use tc_prng::philox::Philox4x32;
+///
+/// let mut rng = Philox4x32::new(42);
+/// let first: u32 = rng.next();
+///
+/// // The same word, without generating anything before it.
+/// assert_eq!(Philox4x32::new(42).at(0)[0], first);
+/// ```
+#[derive(Copy, Clone, Ord, PartialOrd, PartialEq, Eq)]
+pub struct Philox4x32 {
+    key: [u32; 2],
+    ctr: u128,
+    buf: [u32; 4],
+    idx: usize,
+}
+
+/// Position of a [`Philox4x32`] generator, see [`Persist`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PhiloxState {
+    pub key: [u32; 2],
+    pub word_pos: u128,
+}
+
+impl Default for Philox4x32 {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Seedable for Philox4x32 {
+    type Seed = [u8; 8];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(u64::from_le_bytes(seed))
+    }
+}
+
+impl Persist for Philox4x32 {
+    type State = PhiloxState;
+
+    const ALGORITHM: Algorithm = Algorithm::Philox;
+    const VARIANT: u8 = 32;
+    const STATE_LEN: usize = 8 + 16;
+
+    fn state(&self) -> PhiloxState {
+        PhiloxState {
+            key: self.key,
+            word_pos: self.word_pos(),
+        }
+    }
+
+    fn from_state(state: PhiloxState) -> Self {
+        let mut rng = Self::from_key(state.key);
+        rng.seek(state.word_pos);
+        rng
+    }
+}
+
+impl Codec for Philox4x32 {
+    fn encode(&self, out: &mut Vec<u8>) {
+        for k in self.key {
+            out.extend_from_slice(&k.to_le_bytes());
+        }
+        out.extend_from_slice(&self.word_pos().to_le_bytes());
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, StateError> {
+        let mut r = Reader(bytes);
+        Ok(Self::from_state(PhiloxState {
+            key: [r.u32(), r.u32()],
+            word_pos: r.u128(),
+        }))
+    }
+}
+
+impl Philox4x32 {
+    const M0: u32 = 0xD2511F53;
+    const M1: u32 = 0xCD9E8D57;
+    const W0: u32 = 0x9E3779B9;
+    const W1: u32 = 0xBB67AE85;
+    const ROUNDS: usize = 10;
+
+    pub fn new(seed: u64) -> Self {
+        Self::from_key([seed as u32, (seed >> 32) as u32])
+    }
+
+    pub fn from_key(key: [u32; 2]) -> Self {
+        Self {
+            key,
+            ctr: 0,
+            buf: [0; 4],
+            idx: 4,
+        }
+    }
+
+    pub fn key(&self) -> [u32; 2] {
+        self.key
+    }
+
+    /// The block for `counter`. Counter words are taken little-endian.
+    pub fn at(&self, counter: u128) -> [u32; 4] {
+        let ctr = [
+            counter as u32,
+            (counter >> 32) as u32,
+            (counter >> 64) as u32,
+            (counter >> 96) as u32,
+        ];
+        Self::block(ctr, self.key)
+    }
+
+    /// Index of the next word of the sequential stream, wrapping at 2^128.
+    pub fn word_pos(&self) -> u128 {
+        // `ctr` already points past the buffered block.
+        self.ctr.wrapping_mul(4).wrapping_sub(4 - self.idx as u128)
+    }
+
+    /// Positions the sequential stream so the next word is word `n`.
+    pub fn seek(&mut self, n: u128) {
+        self.ctr = n / 4;
+        self.idx = (n % 4) as usize;
+        self.buf = self.at(self.ctr);
+        self.ctr = self.ctr.wrapping_add(1);
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<V>(&mut self) -> V
+    where
+        Self: Prng<V>,
+    {
+        Prng::next_val(self)
+    }
+
+    fn block(mut ctr: [u32; 4], mut key: [u32; 2]) -> [u32; 4] {
+        for r in 0..Self::ROUNDS {
+            if r > 0 {
+                key[0] = key[0].wrapping_add(Self::W0);
+                key[1] = key[1].wrapping_add(Self::W1);
+            }
+            let p0 = Self::M0 as u64 * ctr[0] as u64;
+            let p1 = Self::M1 as u64 * ctr[2] as u64;
+            ctr = [
+                (p1 >> 32) as u32 ^ ctr[1] ^ key[0],
+                p1 as u32,
+                (p0 >> 32) as u32 ^ ctr[3] ^ key[1],
+                p0 as u32,
+            ];
+        }
+        ctr
+    }
+}
+
+impl PrngCore for Philox4x32 {
+    type Word = u32;
+
+    fn calc(&mut self) -> u32 {
+        if self.idx == 4 {
+            self.buf = self.at(self.ctr);
+            self.ctr = self.ctr.wrapping_add(1);
+            self.idx = 0;
+        }
+        let v = self.buf[self.idx];
+        self.idx += 1;
+        v
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn kat(ctr: [u32; 4], key: [u32; 2]) -> [u32; 4] {
+        let c = ctr.iter().rev().fold(0_u128, |c, w| (c << 32) | *w as u128);
+        Philox4x32::from_key(key).at(c)
+    }
+
+    #[test]
+    fn random123_known_answers() {
+        assert_eq!(
+            kat([0; 4], [0; 2]),
+            [0x6627e8d5, 0xe169c58d, 0xbc57ac4c, 0x9b00dbd8]
+        );
+        assert_eq!(
+            kat([u32::MAX; 4], [u32::MAX; 2]),
+            [0x408f276d, 0x41c83b0e, 0xa20bc7c6, 0x6d5451fd]
+        );
+        assert_eq!(
+            kat(
+                [0x243f6a88, 0x85a308d3, 0x13198a2e, 0x03707344],
+                [0xa4093822, 0x299f31d0]
+            ),
+            [0xd16cfe09, 0x94fdcceb, 0x5001e420, 0x24126ea1]
+        );
+    }
+
+    #[test]
+    fn sequential_matches_random_access() {
+        let mut rng = Philox4x32::new(99);
+        let words: Vec<u32> = (0..40).map(|_| rng.next()).collect();
+        for (n, w) in words.iter().enumerate() {
+            let mut r = Philox4x32::new(99);
+            r.seek(n as u128);
+            assert_eq!(r.next::<u32>(), *w);
+            assert_eq!(r.at(n as u128 / 4)[n % 4], *w);
+        }
+    }
+
+    #[test]
+    fn dump_value_types() {
+        let mut rng = Philox4x32::new(123456);
+
+        let a: u8 = rng.next();
+        assert_eq!(a, 13);
+
+        let b: u128 = rng.next();
+        assert_eq!(b, 162919585766329021709996741568895211644);
+
+        let c: bool = rng.next();
+        assert!(!c);
+
+        let d: f32 = rng.next();
+        assert_eq!(d, 0.250726);
+    }
+}
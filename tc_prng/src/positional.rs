@@ -0,0 +1,141 @@
+//! Stateless random values addressed by a seed and integer coordinates.
+//!
+//! Every function here is a pure function of `(seed, coords)`: the value at
+//! a coordinate never depends on which other coordinates were visited or in
+//! which order, which makes it suitable for chunked world generation.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tc_prng::positional::*;
+//!
+//! let tree: bool = rng_at(42, (10, -3));
+//! let height: f32 = rng_at(42, (10, -3, 7));
+//! assert_eq!(tree, rng_at::<bool, _>(42, (10, -3)));
+//!
+//! let mut chunk = xorshiro_at(42, [4, 9]);
+//! let ore: u8 = chunk.next();
+//! ```
+
+use crate::{split_mix::SplitMix64, xorshiro, xorshiro::Xorshiro64, Prng};
+
+/// A single integer coordinate.
+pub trait Coord: Copy {
+    fn to_word(self) -> u64;
+}
+
+macro_rules! impl_coord {
+    ($($t:ty),*) => {
+        $(
+            impl Coord for $t {
+                fn to_word(self) -> u64 {
+                    // Sign extend so -1_i32 and -1_i64 address the same cell.
+                    self as i64 as u64
+                }
+            }
+        )*
+    };
+}
+
+impl_coord!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// A point made of one or more integer coordinates.
+pub trait Coords {
+    /// Folds the coordinates, in order, into `seed`.
+    fn hash(self, seed: u64) -> u64;
+}
+
+/// Absorbs one coordinate with a single `SplitMix64` step.
+fn absorb(h: u64, c: u64) -> u64 {
+    SplitMix64::new(h ^ c).next()
+}
+
+impl<C: Coord> Coords for C {
+    fn hash(self, seed: u64) -> u64 {
+        absorb(seed, self.to_word())
+    }
+}
+
+impl<C: Coord, const N: usize> Coords for [C; N] {
+    fn hash(self, seed: u64) -> u64 {
+        self.iter().fold(seed, |h, c| absorb(h, c.to_word()))
+    }
+}
+
+macro_rules! impl_coords_tuple {
+    ($($name:ident),*) => {
+        impl<$($name: Coord),*> Coords for ($($name,)*) {
+            #[allow(non_snake_case)]
+            fn hash(self, seed: u64) -> u64 {
+                let ($($name,)*) = self;
+                let h = seed;
+                $(let h = absorb(h, $name.to_word());)*
+                h
+            }
+        }
+    };
+}
+
+impl_coords_tuple!(A, B);
+impl_coords_tuple!(A, B, C);
+impl_coords_tuple!(A, B, C, D);
+
+/// The 64-bit hash of `coords` under `seed`.
+pub fn hash_at<C: Coords>(seed: u64, coords: C) -> u64 {
+    coords.hash(seed)
+}
+
+/// A value of any type the 64-bit conversions support, at `coords`.
+pub fn rng_at<T, C: Coords>(seed: u64, coords: C) -> T
+where
+    SplitMix64: Prng<T>,
+{
+    split_mix_at(seed, coords).next()
+}
+
+/// A `SplitMix64` seeded for `coords`, for drawing several values per cell.
+pub fn split_mix_at<C: Coords>(seed: u64, coords: C) -> SplitMix64 {
+    SplitMix64::new(hash_at(seed, coords))
+}
+
+/// A `Xorshiro64` seeded for `coords`, for drawing several values per cell.
+pub fn xorshiro_at<C: Coords>(seed: u64, coords: C) -> Xorshiro64 {
+    xorshiro(hash_at(seed, coords))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn order_independent() {
+        let forward: Vec<u64> = (0..64).map(|x| rng_at(7, (x, 3))).collect();
+        let backward: Vec<u64> = (0..64).rev().map(|x| rng_at(7, (x, 3))).collect();
+        assert!(forward.iter().eq(backward.iter().rev()));
+    }
+
+    #[test]
+    fn coordinates_are_distinct() {
+        assert_eq!(hash_at(1, (2, 3)), hash_at(1, [2_i64, 3]));
+        assert_eq!(hash_at(1, -1_i32), hash_at(1, -1_i64));
+        assert_ne!(hash_at(1, (2, 3)), hash_at(1, (3, 2)));
+        assert_ne!(hash_at(1, (2, 3)), hash_at(2, (2, 3)));
+        assert_ne!(hash_at(1, (2, 3)), hash_at(1, (2, 3, 0)));
+        assert_ne!(hash_at(0, (0, 0)), 0);
+    }
+
+    #[test]
+    fn dump_value_types() {
+        let a: u8 = rng_at(123456, (1, 2));
+        assert_eq!(a, 78);
+
+        let b: u128 = rng_at(123456, (1, 2, 3));
+        assert_eq!(b, 40237443512959054650121553044370842557);
+
+        let c: bool = rng_at(123456, -5);
+        assert!(!c);
+
+        let d: f32 = xorshiro_at(123456, [9, 9]).next();
+        assert_eq!(d, 0.052311666);
+    }
+}
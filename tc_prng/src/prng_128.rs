@@ -1,102 +1,100 @@
-pub trait Prng<T> {
-    fn next_val(&mut self) -> T;
-}
+use crate::{FromWord, PrngCore};
 
-pub(crate) trait Prng128 {
-    fn calc(&mut self) -> u128;
-}
+/// Marker for generators producing 128-bit words.
+pub trait Prng128: PrngCore<Word = u128> {}
+
+impl<T: PrngCore<Word = u128> + ?Sized> Prng128 for T {}
 
-impl<T: Prng128> Prng<i8> for T {
-    fn next_val(&mut self) -> i8 {
-        let v = self.calc();
+impl FromWord<u128> for i8 {
+    fn from_word<G: Prng128 + ?Sized>(rng: &mut G) -> Self {
+        let v = rng.calc();
         (v >> 72) as i8
     }
 }
 
-impl<T: Prng128> Prng<i16> for T {
-    fn next_val(&mut self) -> i16 {
-        let v = self.calc();
+impl FromWord<u128> for i16 {
+    fn from_word<G: Prng128 + ?Sized>(rng: &mut G) -> Self {
+        let v = rng.calc();
         (v >> 64) as i16
     }
 }
 
-impl<T: Prng128> Prng<i32> for T {
-    fn next_val(&mut self) -> i32 {
-        let v = self.calc();
+impl FromWord<u128> for i32 {
+    fn from_word<G: Prng128 + ?Sized>(rng: &mut G) -> Self {
+        let v = rng.calc();
         (v >> 64) as i32
     }
 }
 
-impl<T: Prng128> Prng<i64> for T {
-    fn next_val(&mut self) -> i64 {
-        let v = self.calc();
+impl FromWord<u128> for i64 {
+    fn from_word<G: Prng128 + ?Sized>(rng: &mut G) -> Self {
+        let v = rng.calc();
         (v >> 32) as i64
     }
 }
 
-impl<T: Prng128> Prng<i128> for T {
-    fn next_val(&mut self) -> i128 {
-        let v = self.calc();
+impl FromWord<u128> for i128 {
+    fn from_word<G: Prng128 + ?Sized>(rng: &mut G) -> Self {
+        let v = rng.calc();
         v as i128
     }
 }
 
-impl<T: Prng128> Prng<u8> for T {
-    fn next_val(&mut self) -> u8 {
-        let v = self.calc();
+impl FromWord<u128> for u8 {
+    fn from_word<G: Prng128 + ?Sized>(rng: &mut G) -> Self {
+        let v = rng.calc();
         (v >> 72) as u8
     }
 }
 
-impl<T: Prng128> Prng<u16> for T {
-    fn next_val(&mut self) -> u16 {
-        let v = self.calc();
+impl FromWord<u128> for u16 {
+    fn from_word<G: Prng128 + ?Sized>(rng: &mut G) -> Self {
+        let v = rng.calc();
         (v >> 64) as u16
     }
 }
 
-impl<T: Prng128> Prng<u32> for T {
-    fn next_val(&mut self) -> u32 {
-        let v = self.calc();
+impl FromWord<u128> for u32 {
+    fn from_word<G: Prng128 + ?Sized>(rng: &mut G) -> Self {
+        let v = rng.calc();
         (v >> 64) as u32
     }
 }
 
-impl<T: Prng128> Prng<u64> for T {
-    fn next_val(&mut self) -> u64 {
-        let v = self.calc();
+impl FromWord<u128> for u64 {
+    fn from_word<G: Prng128 + ?Sized>(rng: &mut G) -> Self {
+        let v = rng.calc();
         (v >> 32) as u64
     }
 }
 
-impl<T: Prng128> Prng<u128> for T {
-    fn next_val(&mut self) -> u128 {
-        let v = self.calc();
-        v
+impl FromWord<u128> for u128 {
+    fn from_word<G: Prng128 + ?Sized>(rng: &mut G) -> Self {
+        rng.calc()
     }
 }
 
-impl<T: Prng128> Prng<f32> for T {
-    fn next_val(&mut self) -> f32 {
+impl FromWord<u128> for f32 {
+    fn from_word<G: Prng128 + ?Sized>(rng: &mut G) -> Self {
         const MASK: u128 = (1 << 23) - 1;
         const D: f32 = MASK as f32;
-        let n = ((self.calc() >> 106) & MASK) as f32;
+        let n = ((rng.calc() >> 106) & MASK) as f32;
         n / D
     }
 }
 
-impl<T: Prng128> Prng<f64> for T {
-    fn next_val(&mut self) -> f64 {
+impl FromWord<u128> for f64 {
+    fn from_word<G: Prng128 + ?Sized>(rng: &mut G) -> Self {
         const MASK: u128 = (1 << 54) - 1;
         const D: f64 = MASK as f64;
-        let n = ((self.calc() >> 74) & MASK) as f64;
+        let n = ((rng.calc() >> 74) & MASK) as f64;
         n / D
     }
 }
 
-impl<T: Prng128> Prng<bool> for T {
-    fn next_val(&mut self) -> bool {
-        let v = self.calc() & (1 << 64);
+impl FromWord<u128> for bool {
+    fn from_word<G: Prng128 + ?Sized>(rng: &mut G) -> Self {
+        let v = rng.calc() & (1 << 64);
         v > 0
     }
 }
@@ -1,103 +1,102 @@
-pub trait Prng<T> {
-    fn next_val(&mut self) -> T;
-}
+use crate::{FromWord, PrngCore};
 
-pub(crate) trait Prng64 {
-    fn calc(&mut self) -> u64;
-}
+/// Marker for generators producing 64-bit words.
+pub trait Prng64: PrngCore<Word = u64> {}
+
+impl<T: PrngCore<Word = u64> + ?Sized> Prng64 for T {}
 
-impl<T: Prng64> Prng<i8> for T {
-    fn next_val(&mut self) -> i8 {
-        let v = self.calc();
+impl FromWord<u64> for i8 {
+    fn from_word<G: Prng64 + ?Sized>(rng: &mut G) -> Self {
+        let v = rng.calc();
         (v >> 48) as i8
     }
 }
 
-impl<T: Prng64> Prng<i16> for T {
-    fn next_val(&mut self) -> i16 {
-        let v = self.calc();
+impl FromWord<u64> for i16 {
+    fn from_word<G: Prng64 + ?Sized>(rng: &mut G) -> Self {
+        let v = rng.calc();
         (v >> 37) as i16
     }
 }
 
-impl<T: Prng64> Prng<i32> for T {
-    fn next_val(&mut self) -> i32 {
-        let v = self.calc();
+impl FromWord<u64> for i32 {
+    fn from_word<G: Prng64 + ?Sized>(rng: &mut G) -> Self {
+        let v = rng.calc();
         (v >> 16) as i32
     }
 }
 
-impl<T: Prng64> Prng<i64> for T {
-    fn next_val(&mut self) -> i64 {
-        let v = self.calc();
+impl FromWord<u64> for i64 {
+    fn from_word<G: Prng64 + ?Sized>(rng: &mut G) -> Self {
+        let v = rng.calc();
         v as i64
     }
 }
 
-impl<T: Prng64> Prng<i128> for T {
-    fn next_val(&mut self) -> i128 {
-        let l = self.calc() as u128;
-        let h = self.calc() as u128;
+impl FromWord<u64> for i128 {
+    fn from_word<G: Prng64 + ?Sized>(rng: &mut G) -> Self {
+        let l = rng.calc() as u128;
+        let h = rng.calc() as u128;
         ((h << 64) | l) as i128
     }
 }
 
-impl<T: Prng64> Prng<u8> for T {
-    fn next_val(&mut self) -> u8 {
-        let v = self.calc();
+impl FromWord<u64> for u8 {
+    fn from_word<G: Prng64 + ?Sized>(rng: &mut G) -> Self {
+        let v = rng.calc();
         (v >> 48) as u8
     }
 }
 
-impl<T: Prng64> Prng<u16> for T {
-    fn next_val(&mut self) -> u16 {
-        let v = self.calc();
+impl FromWord<u64> for u16 {
+    fn from_word<G: Prng64 + ?Sized>(rng: &mut G) -> Self {
+        let v = rng.calc();
         (v >> 37) as u16
     }
 }
 
-impl<T: Prng64> Prng<u32> for T {
-    fn next_val(&mut self) -> u32 {
-        let v = self.calc();
+impl FromWord<u64> for u32 {
+    fn from_word<G: Prng64 + ?Sized>(rng: &mut G) -> Self {
+        let v = rng.calc();
         (v >> 16) as u32
     }
 }
 
-impl<T: Prng64> Prng<u64> for T {
-    fn next_val(&mut self) -> u64 {
-        self.calc()
+impl FromWord<u64> for u64 {
+    fn from_word<G: Prng64 + ?Sized>(rng: &mut G) -> Self {
+        rng.calc()
     }
 }
 
-impl<T: Prng64> Prng<u128> for T {
-    fn next_val(&mut self) -> u128 {
-        let l = self.calc() as u128;
-        let h = self.calc() as u128;
+impl FromWord<u64> for u128 {
+    fn from_word<G: Prng64 + ?Sized>(rng: &mut G) -> Self {
+        let l = rng.calc() as u128;
+        let h = rng.calc() as u128;
         (h << 64) | l
     }
 }
 
-impl<T: Prng64> Prng<f32> for T {
-    fn next_val(&mut self) -> f32 {
+impl FromWord<u64> for f32 {
+    fn from_word<G: Prng64 + ?Sized>(rng: &mut G) -> Self {
         const MASK: u64 = (1 << 23) - 1;
         const D: f32 = MASK as f32;
-        let n = ((self.calc() >> 42) & MASK) as f32;
+        let n = ((rng.calc() >> 42) & MASK) as f32;
         n / D
     }
 }
 
-impl<T: Prng64> Prng<f64> for T {
-    fn next_val(&mut self) -> f64 {
+impl FromWord<u64> for f64 {
+    fn from_word<G: Prng64 + ?Sized>(rng: &mut G) -> Self {
         const MASK: u64 = (1 << 54) - 1;
         const D: f64 = MASK as f64;
-        let n = ((self.calc() >> 10) & MASK) as f64;
+        let n = ((rng.calc() >> 10) & MASK) as f64;
         n / D
     }
 }
 
-impl<T: Prng64> Prng<bool> for T {
-    fn next_val(&mut self) -> bool {
-        let v = self.calc() & (1 << 32);
+impl FromWord<u64> for bool {
+    fn from_word<G: Prng64 + ?Sized>(rng: &mut G) -> Self {
+        let v = rng.calc() & (1 << 32);
         v > 0
     }
 }
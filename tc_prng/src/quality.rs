@@ -0,0 +1,516 @@
+//! Statistical tests for generators and distributions.
+//!
+//! Each test draws from any generator and returns a [`TestResult`] with a
+//! p-value: the probability that a perfect generator would give a result at
+//! least as extreme. A working generator gives p-values spread uniformly
+//! over `[0, 1]`. A broken one gives p-values close to 0, or close to 1 when
+//! its output is too regular, as a counter is.
+//!
+//! | test                     | detects                                     |
+//! |--------------------------|---------------------------------------------|
+//! | [`byte_frequency`]       | uneven byte values (chi-square, 255 df)     |
+//! | [`bit_frequency`]        | a biased bit position (chi-square, 32 df)   |
+//! | [`serial_correlation`]   | correlation between consecutive values      |
+//! | [`runs`]                 | bits that change too often or too rarely    |
+//! | [`gap`]                  | irregular gaps between values in `[0, 1/4)` |
+//! | [`birthday_spacings`]    | lattice structure, e.g. in LCGs             |
+//! | [`matrix_rank`]          | linear dependencies over GF(2)              |
+//! | [`kolmogorov_smirnov`]   | a non-uniform distribution of values        |
+//!
+//! The tests read the raw words of the generator, split into 32-bit chunks
+//! with the low half first, so every output bit is tested. Generators whose
+//! raw words leave bits unused, such as a 15-bit `rand()` returned as a
+//! `u32`, fail as expected. [`chi_square`] and [`ks_test`] check arbitrary samples, such
+//! as the output of a distribution.
+//!
+//! The results of a fixed seed never change, so a test can assert them in
+//! regular `cargo test` runs.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tc_prng::{prelude::*, quality};
+//!
+//! let mut rng = xorshiro(42_u64);
+//! for result in quality::battery(&mut rng) {
+//!     assert!(result.passes(0.001), "{result}");
+//! }
+//!
+//! // Distribution of a range: 6 faces, equally likely.
+//! let mut counts = [0; 6];
+//! for _ in 0..6000 {
+//!     counts[rng.next_range(0..6_usize)] += 1;
+//! }
+//! let fair = quality::chi_square("d6", &counts, &[1000.0; 6]);
+//! assert!(fair.passes(0.001), "{fair}");
+//! ```
+
+pub(crate) mod special;
+
+use std::fmt;
+
+use crate::PrngCore;
+
+use special::{chi_square_sf, kolmogorov_sf, normal_two_sided};
+
+/// Outcome of one test.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TestResult {
+    pub name: &'static str,
+    pub statistic: f64,
+    pub p_value: f64,
+}
+
+impl TestResult {
+    /// Whether the p-value lies in `[alpha, 1 - alpha]`, rejecting output
+    /// that is too regular as well as output that is too irregular.
+    pub fn passes(&self, alpha: f64) -> bool {
+        (alpha..=1.0 - alpha).contains(&self.p_value)
+    }
+}
+
+impl fmt::Display for TestResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: statistic {:.4}, p = {:.6}",
+            self.name, self.statistic, self.p_value
+        )
+    }
+}
+
+/// Runs every test with sizes that take a few milliseconds in a release
+/// build.
+pub fn battery<G>(rng: &mut G) -> Vec<TestResult>
+where
+    G: PrngCore + ?Sized,
+    G::Word: Into<u128>,
+{
+    vec![
+        byte_frequency(rng, 1 << 20),
+        bit_frequency(rng, 1 << 16),
+        serial_correlation(rng, 1 << 16),
+        runs(rng, 1 << 20),
+        gap(rng, 10_000),
+        birthday_spacings(rng, 500),
+        matrix_rank(rng, 2_000),
+        kolmogorov_smirnov(rng, 10_000),
+    ]
+}
+
+/// Chi-square test of `observed` counts against `expected` counts.
+///
+/// # Panics
+///
+/// If the slices differ in length or have fewer than two bins.
+pub fn chi_square(name: &'static str, observed: &[u64], expected: &[f64]) -> TestResult {
+    assert_eq!(observed.len(), expected.len(), "one expectation per bin");
+    assert!(observed.len() > 1, "at least two bins");
+    let statistic = observed
+        .iter()
+        .zip(expected)
+        .map(|(&o, &e)| (o as f64 - e).powi(2) / e)
+        .sum();
+    TestResult {
+        name,
+        statistic,
+        p_value: chi_square_sf(statistic, (observed.len() - 1) as f64),
+    }
+}
+
+/// Kolmogorov-Smirnov test of `samples` against the distribution with
+/// cumulative distribution function `cdf`.
+///
+/// # Panics
+///
+/// If `samples` is empty or contains NaN.
+pub fn ks_test(name: &'static str, samples: &[f64], cdf: impl Fn(f64) -> f64) -> TestResult {
+    assert!(!samples.is_empty(), "no samples");
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).expect("NaN sample"));
+    let n = sorted.len() as f64;
+    let statistic = sorted
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| {
+            let f = cdf(x);
+            (f - i as f64 / n).max((i + 1) as f64 / n - f)
+        })
+        .fold(0.0, f64::max);
+    TestResult {
+        name,
+        statistic,
+        p_value: kolmogorov_sf(statistic, sorted.len()),
+    }
+}
+
+/// Frequencies of the 256 byte values in `bytes` bytes of output.
+pub fn byte_frequency<G>(rng: &mut G, bytes: usize) -> TestResult
+where
+    G: PrngCore + ?Sized,
+    G::Word: Into<u128>,
+{
+    let mut counts = [0_u64; 256];
+    for w in Words32::new(rng).take(bytes.div_ceil(4)) {
+        for b in w.to_le_bytes() {
+            counts[b as usize] += 1;
+        }
+    }
+    let expected = [counts.iter().sum::<u64>() as f64 / 256.0; 256];
+    chi_square("byte frequency", &counts, &expected)
+}
+
+/// Frequency of ones at each of the 32 bit positions over `words` 32-bit
+/// chunks, summed as a chi-square with 32 degrees of freedom.
+pub fn bit_frequency<G>(rng: &mut G, words: usize) -> TestResult
+where
+    G: PrngCore + ?Sized,
+    G::Word: Into<u128>,
+{
+    let mut ones = [0_u64; 32];
+    for w in Words32::new(rng).take(words) {
+        for (bit, count) in ones.iter_mut().enumerate() {
+            *count += (w >> bit & 1) as u64;
+        }
+    }
+    let n = words as f64;
+    let statistic = ones
+        .iter()
+        .map(|&k| (k as f64 - n / 2.0).powi(2) / (n / 4.0))
+        .sum();
+    TestResult {
+        name: "bit frequency",
+        statistic,
+        p_value: chi_square_sf(statistic, 32.0),
+    }
+}
+
+/// Lag-1 correlation of `n` uniform values, as `ent` computes it. The
+/// statistic is the coefficient; `sqrt(n)` times it is standard normal.
+pub fn serial_correlation<G>(rng: &mut G, n: usize) -> TestResult
+where
+    G: PrngCore + ?Sized,
+    G::Word: Into<u128>,
+{
+    let u: Vec<f64> = Words32::new(rng).take(n).map(uniform).collect();
+    let (mut sum, mut sum_sq, mut sum_lag) = (0.0, 0.0, 0.0);
+    for (i, &x) in u.iter().enumerate() {
+        sum += x;
+        sum_sq += x * x;
+        sum_lag += x * u[(i + 1) % n];
+    }
+    let n = n as f64;
+    let statistic = (n * sum_lag - sum * sum) / (n * sum_sq - sum * sum);
+    TestResult {
+        name: "serial correlation",
+        statistic,
+        p_value: normal_two_sided(statistic * n.sqrt()),
+    }
+}
+
+/// NIST SP 800-22 runs test: the number of runs of equal bits in `bits`
+/// bits of output.
+pub fn runs<G>(rng: &mut G, bits: usize) -> TestResult
+where
+    G: PrngCore + ?Sized,
+    G::Word: Into<u128>,
+{
+    let words: Vec<u32> = Words32::new(rng).take(bits.div_ceil(32)).collect();
+    let bit = |i: usize| words[i / 32] >> (i % 32) & 1;
+    let n = bits as f64;
+    let pi = (0..bits).map(|i| bit(i) as f64).sum::<f64>() / n;
+    let changes = (1..bits).filter(|&i| bit(i) != bit(i - 1)).count();
+    let statistic = (changes + 1) as f64;
+
+    // The test presumes the frequency of ones is plausible.
+    let p_value = if (pi - 0.5).abs() >= 2.0 / n.sqrt() {
+        0.0
+    } else {
+        let mean = 2.0 * n * pi * (1.0 - pi);
+        let z = (statistic - mean) / (2.0 * n.sqrt() * pi * (1.0 - pi));
+        normal_two_sided(z)
+    };
+    TestResult {
+        name: "runs",
+        statistic,
+        p_value,
+    }
+}
+
+/// Knuth's gap test: lengths of `gaps` gaps between uniform values falling
+/// in `[0, 1/4)`, binned as `0..16` and `16 or more`. A gap stops drawing at
+/// 16, so a generator that never lands in the interval fails instead of
+/// hanging.
+pub fn gap<G>(rng: &mut G, gaps: usize) -> TestResult
+where
+    G: PrngCore + ?Sized,
+    G::Word: Into<u128>,
+{
+    const P: f64 = 0.25;
+    const T: usize = 16;
+    let mut counts = [0_u64; T + 1];
+    let mut words = Words32::new(rng);
+    for _ in 0..gaps {
+        let len = words
+            .by_ref()
+            .take(T)
+            .take_while(|&w| uniform(w) >= P)
+            .count();
+        counts[len] += 1;
+    }
+    let n = gaps as f64;
+    let expected: Vec<f64> = (0..=T)
+        .map(|r| match r {
+            T => n * (1.0 - P).powi(T as i32),
+            r => n * P * (1.0 - P).powi(r as i32),
+        })
+        .collect();
+    chi_square("gap", &counts, &expected)
+}
+
+/// Marsaglia's birthday spacings test, `samples` times: 512 birthdays in a
+/// year of 2^24 days, taken from the top 24 bits of a chunk. The number of
+/// repeated spacings is Poisson with mean 2, binned as `0..6` and `6 or
+/// more`.
+pub fn birthday_spacings<G>(rng: &mut G, samples: usize) -> TestResult
+where
+    G: PrngCore + ?Sized,
+    G::Word: Into<u128>,
+{
+    const M: usize = 512;
+    const LAMBDA: f64 = 2.0; // M^3 / (4 * 2^24)
+    const BINS: usize = 7;
+    let mut counts = [0_u64; BINS];
+    let mut words = Words32::new(rng);
+    for _ in 0..samples {
+        let mut days: Vec<u32> = words.by_ref().take(M).map(|w| w >> 8).collect();
+        days.sort_unstable();
+        let mut spacings: Vec<u32> = std::iter::once(days[0])
+            .chain(days.windows(2).map(|d| d[1] - d[0]))
+            .collect();
+        spacings.sort_unstable();
+        let repeats = spacings.windows(2).filter(|s| s[0] == s[1]).count();
+        counts[repeats.min(BINS - 1)] += 1;
+    }
+
+    let n = samples as f64;
+    let mut poisson = (-LAMBDA).exp();
+    let mut expected = [0.0; BINS];
+    for (k, e) in expected.iter_mut().take(BINS - 1).enumerate() {
+        *e = n * poisson;
+        poisson *= LAMBDA / (k + 1) as f64;
+    }
+    expected[BINS - 1] = n - expected.iter().sum::<f64>();
+    chi_square("birthday spacings", &counts, &expected)
+}
+
+/// Ranks over GF(2) of `matrices` 32x32 bit matrices, one chunk per row,
+/// binned as 32, 31, 30 and 29 or less.
+pub fn matrix_rank<G>(rng: &mut G, matrices: usize) -> TestResult
+where
+    G: PrngCore + ?Sized,
+    G::Word: Into<u128>,
+{
+    let mut counts = [0_u64; 4];
+    let mut words = Words32::new(rng);
+    for _ in 0..matrices {
+        let mut rows = [0_u32; 32];
+        rows.iter_mut().for_each(|r| *r = words.next().unwrap());
+        counts[(32 - rank(rows)).min(3)] += 1;
+    }
+
+    let n = matrices as f64;
+    let p: Vec<f64> = (30..=32).rev().map(rank_probability).collect();
+    let expected = [
+        n * p[0],
+        n * p[1],
+        n * p[2],
+        n * (1.0 - p.iter().sum::<f64>()),
+    ];
+    chi_square("matrix rank", &counts, &expected)
+}
+
+/// Kolmogorov-Smirnov test of `n` values converted to `[0, 1)` against the
+/// uniform distribution.
+pub fn kolmogorov_smirnov<G>(rng: &mut G, n: usize) -> TestResult
+where
+    G: PrngCore + ?Sized,
+    G::Word: Into<u128>,
+{
+    let samples: Vec<f64> = Words32::new(rng).take(n).map(uniform).collect();
+    ks_test("kolmogorov-smirnov", &samples, |x| x)
+}
+
+/// Maps a chunk to the middle of its interval in `(0, 1)`.
+fn uniform(w: u32) -> f64 {
+    (w as f64 + 0.5) / 4_294_967_296.0
+}
+
+/// Rank over GF(2) of the matrix with the given rows.
+fn rank(mut rows: [u32; 32]) -> usize {
+    let mut rank = 0;
+    for bit in (0..32).rev() {
+        let Some(pivot) = (rank..32).find(|&r| rows[r] >> bit & 1 == 1) else {
+            continue;
+        };
+        rows.swap(rank, pivot);
+        for r in 0..32 {
+            if r != rank && rows[r] >> bit & 1 == 1 {
+                rows[r] ^= rows[rank];
+            }
+        }
+        rank += 1;
+    }
+    rank
+}
+
+/// Probability that a random 32x32 matrix over GF(2) has rank `r`.
+fn rank_probability(r: usize) -> f64 {
+    let m = 32;
+    let exponent = (r * (2 * m - r)) as f64 - (m * m) as f64;
+    (0..r).fold(2_f64.powf(exponent), |p, i| {
+        let row = 1.0 - 2_f64.powi(i as i32 - m as i32);
+        p * row * row / (1.0 - 2_f64.powi(i as i32 - r as i32))
+    })
+}
+
+/// Raw words of a generator as 32-bit chunks, low half first.
+struct Words32<'a, G: PrngCore + ?Sized> {
+    rng: &'a mut G,
+    buf: u128,
+    left: u32,
+}
+
+impl<'a, G> Words32<'a, G>
+where
+    G: PrngCore + ?Sized,
+    G::Word: Into<u128>,
+{
+    const CHUNKS: u32 = std::mem::size_of::<G::Word>() as u32 / 4;
+
+    fn new(rng: &'a mut G) -> Self {
+        Self {
+            rng,
+            buf: 0,
+            left: 0,
+        }
+    }
+}
+
+impl<G> Iterator for Words32<'_, G>
+where
+    G: PrngCore + ?Sized,
+    G::Word: Into<u128>,
+{
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.left == 0 {
+            self.buf = self.rng.calc().into();
+            self.left = Self::CHUNKS;
+        }
+        let w = self.buf as u32;
+        self.buf >>= 32;
+        self.left -= 1;
+        Some(w)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        chacha::ChaCha8, mock::StepRng, mt::Mt19937, prelude::*, sfc::Sfc64, split_mix::SplitMix128,
+    };
+
+    fn assert_passes<G>(mut rng: G)
+    where
+        G: PrngCore,
+        G::Word: Into<u128>,
+    {
+        for result in battery(&mut rng) {
+            assert!(result.passes(0.001), "{result}");
+        }
+    }
+
+    #[test]
+    fn good_generators_pass() {
+        assert_passes(xorshiro(1_u64));
+        assert_passes(split_mix(2_u32));
+        assert_passes(SplitMix128::new(3));
+        assert_passes(ChaCha8::new([4; 32]));
+        assert_passes(Mt19937::new(5));
+        assert_passes(Sfc64::new(6));
+    }
+
+    #[test]
+    fn counter_is_too_regular() {
+        let mut rng = StepRng::new(0_u32, 1);
+        assert!(!byte_frequency(&mut rng, 1 << 16).passes(0.001));
+        let mut rng = StepRng::new(0_u64, 0x9e37_79b9_7f4a_7c15);
+        assert!(!matrix_rank(&mut rng, 1000).passes(0.001));
+        assert!(!serial_correlation(&mut StepRng::new(0_u32, 1 << 20), 1000).passes(0.001));
+    }
+
+    #[test]
+    fn stuck_generator_fails_gap() {
+        // Never below 1/4, so every gap is cut off at the top bin.
+        let mut rng = StepRng::new(u32::MAX, 0);
+        assert!(!gap(&mut rng, 100).passes(0.001));
+    }
+
+    #[test]
+    fn narrow_words_fail() {
+        // 15 bits per 32-bit word, like a C `rand()` with RAND_MAX 32767.
+        struct Narrow(crate::xorshiro::Xorshiro32);
+        impl PrngCore for Narrow {
+            type Word = u32;
+            fn calc(&mut self) -> u32 {
+                self.0.calc() >> 17
+            }
+        }
+        let mut rng = Narrow(xorshiro(1_u32));
+        assert!(!bit_frequency(&mut rng, 10_000).passes(0.001));
+        assert!(!runs(&mut rng, 100_000).passes(0.001));
+        assert!(!kolmogorov_smirnov(&mut rng, 1000).passes(0.001));
+    }
+
+    #[test]
+    fn biased_bits_fail() {
+        // Every word has its top bit set.
+        struct TopBit(Xorshiro64);
+        impl PrngCore for TopBit {
+            type Word = u64;
+            fn calc(&mut self) -> u64 {
+                self.0.calc() | 1 << 63
+            }
+        }
+        let mut rng = TopBit(xorshiro(1_u64));
+        assert!(!bit_frequency(&mut rng, 10_000).passes(0.001));
+        assert!(!gap(&mut rng, 10_000).passes(0.001));
+    }
+
+    #[test]
+    fn rank_probabilities() {
+        assert!((rank_probability(32) - 0.288_788).abs() < 1e-6);
+        assert!((rank_probability(31) - 0.577_576).abs() < 1e-6);
+        assert!((rank_probability(30) - 0.128_350).abs() < 1e-6);
+        assert_eq!(rank([1 << 5; 32]), 1);
+        assert_eq!(rank(std::array::from_fn(|i| 1 << i)), 32);
+    }
+
+    #[test]
+    fn distribution_helpers() {
+        let mut rng = xorshiro(7_u64);
+        let samples: Vec<f64> = (0..5000).map(|_| rng.next_range(-1.0..1.0)).collect();
+        assert!(ks_test("range", &samples, |x| (x + 1.0) / 2.0).passes(0.001));
+        // Wrong distribution.
+        assert!(!ks_test("range", &samples, |x| x.clamp(0.0, 1.0)).passes(0.001));
+
+        let fair = chi_square("coin", &[5000, 5000], &[5000.0, 5000.0]);
+        assert_eq!(fair.statistic, 0.0);
+        assert_eq!(fair.p_value, 1.0);
+        let biased = chi_square("coin", &[5300, 4700], &[5000.0, 5000.0]);
+        assert!(biased.p_value < 1e-8);
+    }
+}
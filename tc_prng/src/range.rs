@@ -0,0 +1,210 @@
+//! Uniform values from a range.
+//!
+//! Integers use Lemire's multiply-and-reject method on 64-bit draws, so every
+//! value of the range is equally likely and most calls take a single word.
+//! Floats scale a 53-bit (`f64`) or 24-bit (`f32`) fraction into the range.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tc_prng::prelude::*;
+//!
+//! let mut rng = xorshiro(7_u64);
+//! let die = rng.next_range(1..=6_u8);
+//! let angle = rng.next_range(0.0..360.0_f32);
+//! assert!((1..=6).contains(&die));
+//! assert!((0.0..360.0).contains(&angle));
+//! ```
+
+use std::ops::{Range, RangeInclusive};
+
+use crate::Prng;
+
+/// A range that values of `T` can be drawn from uniformly.
+///
+/// Implemented for `Range` and `RangeInclusive` of every integer type up to
+/// 64 bits and of `f32` and `f64`. Sampling panics if the range is empty.
+pub trait SampleRange<T> {
+    fn sample<G: Prng<u64> + ?Sized>(self, rng: &mut G) -> T;
+}
+
+/// Uniform value in `0..s`, or any `u64` when `s` is 0.
+fn bounded<G: Prng<u64> + ?Sized>(rng: &mut G, s: u64) -> u64 {
+    if s == 0 {
+        return rng.next_val();
+    }
+    let mut m = rng.next_val() as u128 * s as u128;
+    if (m as u64) < s {
+        let threshold = s.wrapping_neg() % s;
+        while (m as u64) < threshold {
+            m = rng.next_val() as u128 * s as u128;
+        }
+    }
+    (m >> 64) as u64
+}
+
+macro_rules! impl_int_range {
+    ($($t:ty => $u:ty),*) => {$(
+        impl SampleRange<$t> for Range<$t> {
+            fn sample<G: Prng<u64> + ?Sized>(self, rng: &mut G) -> $t {
+                assert!(self.start < self.end, "cannot sample empty range");
+                let span = self.end.wrapping_sub(self.start) as $u as u64;
+                self.start.wrapping_add(bounded(rng, span) as $u as $t)
+            }
+        }
+
+        impl SampleRange<$t> for RangeInclusive<$t> {
+            fn sample<G: Prng<u64> + ?Sized>(self, rng: &mut G) -> $t {
+                let (low, high) = self.into_inner();
+                assert!(low <= high, "cannot sample empty range");
+                // Wraps to 0, meaning every value, only for 64-bit types.
+                let span = (high.wrapping_sub(low) as $u as u64).wrapping_add(1);
+                low.wrapping_add(bounded(rng, span) as $u as $t)
+            }
+        }
+    )*};
+}
+
+impl_int_range!(
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, usize => usize,
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, isize => usize
+);
+
+/// `start + (end - start) * u`, or, when the width overflows as in
+/// `f64::MIN..f64::MAX`, the same point weighted from both ends.
+macro_rules! lerp {
+    ($start:expr, $end:expr, $u:expr) => {{
+        let (start, end, u) = ($start, $end, $u);
+        let width = end - start;
+        if width.is_finite() {
+            start + width * u
+        } else {
+            start * (1.0 - u) + end * u
+        }
+    }};
+}
+pub(crate) use lerp;
+
+macro_rules! impl_float_range {
+    ($($t:ty, $bits:expr);*) => {$(
+        impl SampleRange<$t> for Range<$t> {
+            fn sample<G: Prng<u64> + ?Sized>(self, rng: &mut G) -> $t {
+                let Range { start, end } = self;
+                assert!(start < end, "cannot sample empty range");
+                assert!(start.is_finite() && end.is_finite(), "cannot sample infinite range");
+                const SCALE: $t = 1.0 / (1_u64 << $bits) as $t;
+                loop {
+                    let u = (rng.next_val() >> (64 - $bits)) as $t * SCALE;
+                    let v = lerp!(start, end, u);
+                    // Rounding can land on `end` for wide ranges.
+                    if v < end {
+                        return v;
+                    }
+                }
+            }
+        }
+
+        impl SampleRange<$t> for RangeInclusive<$t> {
+            fn sample<G: Prng<u64> + ?Sized>(self, rng: &mut G) -> $t {
+                let (low, high) = self.into_inner();
+                assert!(low <= high, "cannot sample empty range");
+                assert!(low.is_finite() && high.is_finite(), "cannot sample infinite range");
+                const SCALE: $t = 1.0 / ((1_u64 << $bits) - 1) as $t;
+                let u = (rng.next_val() >> (64 - $bits)) as $t * SCALE;
+                lerp!(low, high, u).min(high)
+            }
+        }
+    )*};
+}
+
+impl_float_range!(f32, 24; f64, 53);
+
+#[cfg(test)]
+mod test {
+    use crate::{mock::ScriptedRng, prelude::*};
+
+    #[test]
+    fn integers_stay_in_range() {
+        let mut rng = xorshiro(3_u64);
+        let mut seen = [false; 6];
+        for _ in 0..1000 {
+            let v = rng.next_range(1..=6_u8);
+            seen[v as usize - 1] = true;
+            let w = rng.next_range(-5..5_i32);
+            assert!((-5..5).contains(&w));
+        }
+        assert!(seen.iter().all(|s| *s));
+    }
+
+    #[test]
+    fn full_and_extreme_ranges() {
+        let mut rng = ScriptedRng::new(vec![u64::MAX, 0, u64::MAX]);
+        assert_eq!(rng.next_range(i64::MIN..=i64::MAX), i64::MAX);
+        assert_eq!(rng.next_range(i8::MIN..=i8::MAX), i8::MIN);
+        assert_eq!(rng.next_range(i8::MIN..=i8::MAX), i8::MAX);
+    }
+
+    #[test]
+    fn rejects_biased_draws() {
+        // With s = 3, products whose low word is below 2^64 % 3 = 1 are
+        // rejected, so the zero word is skipped.
+        let mut rng = ScriptedRng::new(vec![0_u64, u64::MAX]);
+        assert_eq!(rng.next_range(0..3_u64), 2);
+        assert_eq!(rng.consumed(), 2);
+    }
+
+    #[test]
+    fn floats() {
+        let mut rng = ScriptedRng::new(vec![0_u64, u64::MAX, 1 << 63, u64::MAX]);
+        assert_eq!(rng.next_range(2.0..4.0_f64), 2.0);
+        // The largest fraction rounds up to `end` and is drawn again.
+        assert_eq!(rng.next_range(2.0..4.0_f64), 3.0);
+        assert_eq!(rng.next_range(2.0..=4.0_f32), 4.0);
+    }
+
+    #[test]
+    fn float_ranges_wider_than_max() {
+        let mut rng = ScriptedRng::new(vec![0_u64, u64::MAX, 1 << 63, 0, u64::MAX]);
+        assert_eq!(rng.next_range(f64::MIN..f64::MAX), f64::MIN);
+        let top = rng.next_range(f64::MIN..f64::MAX);
+        assert!(top > f64::MAX * 0.99 && top < f64::MAX);
+        assert_eq!(rng.next_range(f64::MIN..f64::MAX), 0.0);
+        assert_eq!(rng.next_range(f32::MIN..=f32::MAX), f32::MIN);
+        assert_eq!(rng.next_range(f32::MIN..=f32::MAX), f32::MAX);
+
+        let mut rng = xorshiro(8_u64);
+        let mut signs = [false; 2];
+        for _ in 0..1000 {
+            let v = rng.next_range(-f32::MAX..f32::MAX);
+            assert!(v.is_finite());
+            signs[usize::from(v > 0.0)] = true;
+        }
+        assert_eq!(signs, [true; 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot sample infinite range")]
+    fn infinite_range_panics() {
+        xorshiro(1_u64).next_range(0.0..f64::INFINITY);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot sample empty range")]
+    fn empty_range_panics() {
+        xorshiro(1_u64).next_range(5..5_u32);
+    }
+
+    #[test]
+    fn dump_value_types() {
+        let mut rng = split_mix(123456_u64);
+
+        let a = rng.next_range(10..20_u8);
+        assert_eq!(a, 12);
+
+        let b = rng.next_range(-1000..=1000_i64);
+        assert_eq!(b, -485);
+
+        let c = rng.next_range(0.0..1.0_f32);
+        assert_eq!(c, 0.5895432);
+    }
+}
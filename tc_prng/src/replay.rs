@@ -0,0 +1,670 @@
+//! Record-and-replay of raw generator output.
+//!
+//! [`Recording`] wraps a generator and logs every request to an
+//! [`io::Write`]: the label (the requested type's [`Tag`], or one given by
+//! the caller) and the raw words the value was built from. [`Replaying`] reads such a log
+//! back and serves the same words, reporting a [`ReplayError`] as soon as the
+//! requests stop matching what was recorded.
+//!
+//! The log starts with `b"tcrp"`, a format version and the word size in
+//! bytes. Each record is a little-endian `u32` label length, the UTF-8
+//! label, a `u32` word count and the words, little-endian.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tc_prng::{prelude::*, replay::{Recording, Replaying}};
+//!
+//! let mut log = Vec::new();
+//! let mut rng = Recording::new(xorshiro(7_u64), &mut log);
+//! let roll: u8 = rng.next();
+//! let spawn: f32 = rng.next_labeled("spawn x");
+//! rng.finish().unwrap();
+//!
+//! let mut replay = Replaying::<u64, _>::new(&log[..]).unwrap();
+//! assert_eq!(replay.next::<u8>(), roll);
+//! assert_eq!(replay.next_labeled::<f32>("spawn x"), spawn);
+//! assert!(replay.try_next::<u8>().is_err());
+//! ```
+
+use std::{
+    fmt,
+    io::{self, Read, Write},
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+    },
+};
+
+use crate::{FromWord, PrngCore};
+
+const MAGIC: [u8; 4] = *b"tcrp";
+const VERSION: u8 = 1;
+const CALC: &str = "calc";
+/// Label of requests made through the generic [`PrngCore::next`].
+const UNTAGGED: &str = "value";
+
+/// The stable name a type is logged under.
+///
+/// `std::any::type_name` may change between compiler versions, so logs use
+/// these instead: the type as written in Rust, e.g. `u8`, `[f32; 3]`,
+/// `(u8, bool)` or `Option<char>`. Implement it for your own types to record
+/// them with `next`.
+pub trait Tag {
+    fn tag(out: &mut String);
+}
+
+macro_rules! impl_tag {
+    ($($t:ident),*) => {$(
+        impl Tag for $t {
+            fn tag(out: &mut String) {
+                out.push_str(stringify!($t));
+            }
+        }
+    )*};
+}
+
+impl_tag!(
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    bool,
+    char,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize
+);
+
+impl<T: Tag> Tag for Wrapping<T> {
+    fn tag(out: &mut String) {
+        out.push_str("Wrapping<");
+        T::tag(out);
+        out.push('>');
+    }
+}
+
+impl<T: Tag> Tag for Option<T> {
+    fn tag(out: &mut String) {
+        out.push_str("Option<");
+        T::tag(out);
+        out.push('>');
+    }
+}
+
+impl<T: Tag, const N: usize> Tag for [T; N] {
+    fn tag(out: &mut String) {
+        out.push('[');
+        T::tag(out);
+        out.push_str(&format!("; {N}]"));
+    }
+}
+
+macro_rules! impl_tuple_tag {
+    ($(($first:ident $(, $t:ident)*)),*) => {$(
+        impl<$first: Tag, $($t: Tag),*> Tag for ($first, $($t,)*) {
+            fn tag(out: &mut String) {
+                out.push('(');
+                $first::tag(out);
+                $(
+                    out.push_str(", ");
+                    $t::tag(out);
+                )*
+                // `(A,)` is a tuple, `(A)` is not.
+                let rest: &[&str] = &[$(stringify!($t)),*];
+                if rest.is_empty() {
+                    out.push(',');
+                }
+                out.push(')');
+            }
+        }
+    )*};
+}
+
+impl_tuple_tag!(
+    (A),
+    (A, B),
+    (A, B, C),
+    (A, B, C, D),
+    (A, B, C, D, E),
+    (A, B, C, D, E, F),
+    (A, B, C, D, E, F, H),
+    (A, B, C, D, E, F, H, I),
+    (A, B, C, D, E, F, H, I, J),
+    (A, B, C, D, E, F, H, I, J, K),
+    (A, B, C, D, E, F, H, I, J, K, L),
+    (A, B, C, D, E, F, H, I, J, K, L, M)
+);
+
+/// The [`Tag`] of `V`.
+pub fn tag<V: Tag>() -> String {
+    let mut out = String::new();
+    V::tag(&mut out);
+    out
+}
+
+/// A raw generator word that can be written to and read from a log.
+pub trait Word: Copy + Default {
+    const BYTES: usize;
+
+    fn write_le(self, out: &mut Vec<u8>);
+
+    /// Reads a word from exactly `BYTES` bytes.
+    fn read_le(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_word {
+    ($($t:ty),*) => {$(
+        impl Word for $t {
+            const BYTES: usize = std::mem::size_of::<$t>();
+
+            fn write_le(self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+
+            fn read_le(bytes: &[u8]) -> Self {
+                <$t>::from_le_bytes(bytes.try_into().unwrap())
+            }
+        }
+    )*};
+}
+
+impl_word!(u32, u64, u128);
+
+/// Why a replay could not continue.
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(io::Error),
+    /// The log does not start with a replay header.
+    BadHeader,
+    /// The log was recorded from a generator with another word size.
+    WordSize {
+        recorded: usize,
+        expected: usize,
+    },
+    /// Request `index` was made but the log has ended.
+    Exhausted {
+        index: u64,
+    },
+    /// Request `index` asked for something other than what was recorded.
+    Diverged {
+        index: u64,
+        recorded: String,
+        requested: String,
+    },
+    /// Request `index` matched its label but used a different number of words.
+    WordCount {
+        index: u64,
+        label: String,
+        recorded: usize,
+        consumed: usize,
+    },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Io(e) => write!(f, "reading the replay log failed: {e}"),
+            ReplayError::BadHeader => write!(f, "not a tc_prng replay log"),
+            ReplayError::WordSize { recorded, expected } => write!(
+                f,
+                "log holds {recorded} byte words, the replaying generator uses {expected}"
+            ),
+            ReplayError::Exhausted { index } => {
+                write!(f, "replay diverged at request {index}: the log has ended")
+            }
+            ReplayError::Diverged {
+                index,
+                recorded,
+                requested,
+            } => write!(
+                f,
+                "replay diverged at request {index}: recorded `{recorded}`, requested `{requested}`"
+            ),
+            ReplayError::WordCount {
+                index,
+                label,
+                recorded,
+                consumed,
+            } => write!(
+                f,
+                "replay diverged at request {index} (`{label}`): recorded {recorded} words, consumed {consumed}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReplayError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ReplayError {
+    fn from(e: io::Error) -> Self {
+        ReplayError::Io(e)
+    }
+}
+
+/// Wraps a generator and logs every request to `W`.
+///
+/// Values requested through [`next`](Self::next) are labelled with their
+/// [`Tag`]; [`next_labeled`](Self::next_labeled) adds a call site label.
+/// Requests through the generic [`PrngCore::next`], where the type may have
+/// no tag, are labelled `value` and only checked by their word count. Raw
+/// [`calc`](PrngCore::calc) calls outside a request are logged one word at a
+/// time as `calc`.
+///
+/// Logging never interrupts the game: the first write error is kept and
+/// returned by [`finish`](Self::finish), and nothing more is written.
+pub struct Recording<G: PrngCore, W: Write> {
+    rng: G,
+    out: W,
+    pending: Option<Vec<G::Word>>,
+    error: Option<io::Error>,
+}
+
+impl<G, W> Recording<G, W>
+where
+    G: PrngCore,
+    G::Word: Word,
+    W: Write,
+{
+    pub fn new(rng: G, out: W) -> Self {
+        let mut header = MAGIC.to_vec();
+        header.extend_from_slice(&[VERSION, G::Word::BYTES as u8]);
+        let mut rec = Self {
+            rng,
+            out,
+            pending: None,
+            error: None,
+        };
+        rec.write(&header);
+        rec
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<V: FromWord<G::Word> + Tag>(&mut self) -> V {
+        self.record(&tag::<V>())
+    }
+
+    pub fn next_labeled<V: FromWord<G::Word> + Tag>(&mut self, label: &str) -> V {
+        self.record(&labeled::<V>(label))
+    }
+
+    pub fn inner(&self) -> &G {
+        &self.rng
+    }
+
+    /// Flushes the log and returns the generator and writer, or the first
+    /// write error.
+    pub fn finish(mut self) -> io::Result<(G, W)> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        self.out.flush()?;
+        Ok((self.rng, self.out))
+    }
+
+    fn record<V: FromWord<G::Word>>(&mut self, label: &str) -> V {
+        if self.pending.is_some() {
+            // Nested request; its words belong to the outer record.
+            return V::from_word(self);
+        }
+        self.pending = Some(Vec::new());
+        let v = V::from_word(self);
+        let words = self.pending.take().unwrap_or_default();
+        self.write_record(label, &words);
+        v
+    }
+
+    fn write_record(&mut self, label: &str, words: &[G::Word]) {
+        let mut buf = Vec::with_capacity(8 + label.len() + words.len() * G::Word::BYTES);
+        buf.extend_from_slice(&(label.len() as u32).to_le_bytes());
+        buf.extend_from_slice(label.as_bytes());
+        buf.extend_from_slice(&(words.len() as u32).to_le_bytes());
+        for w in words {
+            w.write_le(&mut buf);
+        }
+        self.write(&buf);
+    }
+
+    fn write(&mut self, buf: &[u8]) {
+        if self.error.is_none() {
+            if let Err(e) = self.out.write_all(buf) {
+                self.error = Some(e);
+            }
+        }
+    }
+}
+
+impl<G, W> PrngCore for Recording<G, W>
+where
+    G: PrngCore,
+    G::Word: Word,
+    W: Write,
+{
+    type Word = G::Word;
+
+    fn calc(&mut self) -> G::Word {
+        let w = self.rng.calc();
+        match &mut self.pending {
+            Some(words) => words.push(w),
+            None => self.write_record(CALC, &[w]),
+        }
+        w
+    }
+
+    fn next<T: FromWord<G::Word>>(&mut self) -> T {
+        self.record(UNTAGGED)
+    }
+}
+
+/// Serves the words of a [`Recording`] log back as a generator.
+///
+/// [`try_next`](Self::try_next) reports divergence as a [`ReplayError`];
+/// `next` and the [`PrngCore`] methods panic with the same message.
+pub struct Replaying<W, R> {
+    input: R,
+    words: Vec<W>,
+    used: usize,
+    short: bool,
+    in_request: bool,
+    index: u64,
+}
+
+impl<W: Word, R: Read> Replaying<W, R> {
+    pub fn new(mut input: R) -> Result<Self, ReplayError> {
+        let mut header = [0; 6];
+        input.read_exact(&mut header)?;
+        if header[..4] != MAGIC || header[4] != VERSION {
+            return Err(ReplayError::BadHeader);
+        }
+        if header[5] as usize != W::BYTES {
+            return Err(ReplayError::WordSize {
+                recorded: header[5] as usize,
+                expected: W::BYTES,
+            });
+        }
+        Ok(Self {
+            input,
+            words: Vec::new(),
+            used: 0,
+            short: false,
+            in_request: false,
+            index: 0,
+        })
+    }
+
+    /// Number of requests replayed so far.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    pub fn try_next<V: FromWord<W> + Tag>(&mut self) -> Result<V, ReplayError> {
+        self.replay(&tag::<V>())
+    }
+
+    pub fn try_next_labeled<V: FromWord<W> + Tag>(
+        &mut self,
+        label: &str,
+    ) -> Result<V, ReplayError> {
+        self.replay(&labeled::<V>(label))
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<V: FromWord<W> + Tag>(&mut self) -> V {
+        self.try_next().unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    pub fn next_labeled<V: FromWord<W> + Tag>(&mut self, label: &str) -> V {
+        self.try_next_labeled(label)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn replay<V: FromWord<W>>(&mut self, label: &str) -> Result<V, ReplayError> {
+        if self.in_request {
+            return Ok(V::from_word(self));
+        }
+        self.load(label)?;
+        self.in_request = true;
+        let v = V::from_word(self);
+        self.in_request = false;
+        self.check(label)?;
+        Ok(v)
+    }
+
+    /// Reads the next record, which must carry `label`.
+    fn load(&mut self, label: &str) -> Result<(), ReplayError> {
+        let index = self.index;
+        let len = match self.read_u32() {
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                return Err(ReplayError::Exhausted { index })
+            }
+            r => r? as usize,
+        };
+        let recorded = self.read_bytes(len as u64)?;
+        let count = self.read_u32()? as u64;
+        let bytes = self.read_bytes(count * W::BYTES as u64)?;
+
+        self.index += 1;
+        let recorded = String::from_utf8_lossy(&recorded);
+        if recorded != label {
+            return Err(ReplayError::Diverged {
+                index,
+                recorded: recorded.into_owned(),
+                requested: label.to_owned(),
+            });
+        }
+        self.words = bytes.chunks_exact(W::BYTES).map(W::read_le).collect();
+        self.used = 0;
+        self.short = false;
+        Ok(())
+    }
+
+    /// Checks that the request used exactly the recorded words.
+    fn check(&self, label: &str) -> Result<(), ReplayError> {
+        if self.short || self.used != self.words.len() {
+            return Err(ReplayError::WordCount {
+                index: self.index - 1,
+                label: label.to_owned(),
+                recorded: self.words.len(),
+                consumed: self.used + self.short as usize,
+            });
+        }
+        Ok(())
+    }
+
+    fn read_u32(&mut self) -> io::Result<u32> {
+        let mut b = [0; 4];
+        self.input.read_exact(&mut b)?;
+        Ok(u32::from_le_bytes(b))
+    }
+
+    /// Reads exactly `len` bytes. The buffer only grows as bytes arrive, so
+    /// a corrupt length cannot allocate more than the log holds.
+    fn read_bytes(&mut self, len: u64) -> io::Result<Vec<u8>> {
+        let mut buf = Vec::new();
+        (&mut self.input).take(len).read_to_end(&mut buf)?;
+        if buf.len() as u64 != len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok(buf)
+    }
+}
+
+impl<W: Word, R: Read> PrngCore for Replaying<W, R> {
+    type Word = W;
+
+    fn calc(&mut self) -> W {
+        if !self.in_request {
+            let r = self.load(CALC);
+            let w = self.words.first().copied().unwrap_or_default();
+            self.used = 1;
+            r.and_then(|_| self.check(CALC))
+                .unwrap_or_else(|e| panic!("{e}"));
+            return w;
+        }
+        match self.words.get(self.used) {
+            Some(w) => {
+                self.used += 1;
+                *w
+            }
+            None => {
+                // Reported by `check` once the request returns.
+                self.short = true;
+                W::default()
+            }
+        }
+    }
+
+    fn next<T: FromWord<W>>(&mut self) -> T {
+        self.replay(UNTAGGED).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+fn labeled<V: Tag>(label: &str) -> String {
+    let mut out = format!("{label}: ");
+    V::tag(&mut out);
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{chacha::ChaCha8, prelude::*};
+
+    fn draws<G: PrngCore<Word = u32>>(rng: &mut G) -> (u8, u128, bool, f32) {
+        (rng.next(), rng.next(), rng.next(), rng.next())
+    }
+
+    #[test]
+    fn replays_recorded_draws() {
+        let mut log = Vec::new();
+        let mut rng = Recording::new(ChaCha8::new([3; 32]), &mut log);
+        let recorded = draws(&mut rng);
+        let raw = rng.calc();
+        let (mut inner, _) = rng.finish().unwrap();
+
+        let mut fresh = ChaCha8::new([3; 32]);
+        assert_eq!(draws(&mut fresh), recorded);
+        assert_eq!(fresh.calc(), raw);
+
+        let mut replay = Replaying::<u32, _>::new(&log[..]).unwrap();
+        assert_eq!(draws(&mut replay), recorded);
+        assert_eq!(replay.calc(), raw);
+        assert_eq!(replay.index(), 5);
+        assert!(matches!(
+            replay.try_next::<u8>(),
+            Err(ReplayError::Exhausted { index: 5 })
+        ));
+
+        // The wrapped generator is untouched by recording.
+        assert_eq!(inner.calc(), fresh.calc());
+    }
+
+    #[test]
+    fn reports_divergence() {
+        let mut log = Vec::new();
+        let mut rng = Recording::new(split_mix(1_u64), &mut log);
+        let _: u32 = rng.next_labeled("damage");
+        let _: u128 = rng.next();
+        rng.finish().unwrap();
+
+        let mut replay = Replaying::<u64, _>::new(&log[..]).unwrap();
+        let e = replay.try_next_labeled::<u32>("crit").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "replay diverged at request 0: recorded `damage: u32`, requested `crit: u32`"
+        );
+        assert!(matches!(
+            replay.try_next::<u64>(),
+            Err(ReplayError::Diverged { index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_other_word_sizes() {
+        let mut log = Vec::new();
+        Recording::new(xorshiro(1_u32), &mut log).finish().unwrap();
+        assert!(matches!(
+            Replaying::<u64, _>::new(&log[..]),
+            Err(ReplayError::WordSize {
+                recorded: 4,
+                expected: 8
+            })
+        ));
+        assert!(matches!(
+            Replaying::<u32, _>::new(&b"nope!!"[..]),
+            Err(ReplayError::BadHeader)
+        ));
+    }
+
+    #[test]
+    fn tags_are_rust_spellings() {
+        assert_eq!(tag::<NonZeroU16>(), "NonZeroU16");
+        assert_eq!(tag::<(u8,)>(), "(u8,)");
+        assert_eq!(
+            tag::<(usize, [f32; 3], Option<Wrapping<char>>)>(),
+            "(usize, [f32; 3], Option<Wrapping<char>>)"
+        );
+
+        let mut log = Vec::new();
+        let mut rng = Recording::new(xorshiro(4_u64), &mut log);
+        let _: [Option<u8>; 2] = rng.next();
+        rng.finish().unwrap();
+        let e = Replaying::<u64, _>::new(&log[..])
+            .unwrap()
+            .try_next::<[u8; 2]>()
+            .unwrap_err();
+        assert!(e
+            .to_string()
+            .ends_with("recorded `[Option<u8>; 2]`, requested `[u8; 2]`"));
+    }
+
+    #[test]
+    fn corrupt_lengths_do_not_allocate() {
+        let mut log = Vec::new();
+        Recording::new(xorshiro(1_u64), &mut log).finish().unwrap();
+        log.extend_from_slice(&u32::MAX.to_le_bytes());
+        log.extend_from_slice(b"u8");
+        let e = Replaying::<u64, _>::new(&log[..])
+            .unwrap()
+            .try_next::<u8>()
+            .unwrap_err();
+        assert!(matches!(e, ReplayError::Io(e) if e.kind() == io::ErrorKind::UnexpectedEof));
+    }
+
+    #[test]
+    #[should_panic(expected = "recorded `u8`, requested `f64`")]
+    fn next_panics_on_divergence() {
+        let mut log = Vec::new();
+        let mut rng = Recording::new(xorshiro(1_u64), &mut log);
+        let _: u8 = rng.next();
+        rng.finish().unwrap();
+
+        let _: f64 = Replaying::<u64, _>::new(&log[..]).unwrap().next();
+    }
+}
@@ -0,0 +1,306 @@
+//! Mark Overton's Romu generators.
+//!
+//! Romu generators combine a multiplication with rotations. The multiply
+//! runs in parallel with the rest of the step, so in a tight loop they cost
+//! about as much as a single multiply. They are chaotic: their period
+//! depends on the seed and is not guaranteed. Overton instead gives a
+//! *capacity*, the amount of output expected before any statistical flaw
+//! shows up.
+//!
+//! | generator       | state     | capacity (bytes) |
+//! |-----------------|-----------|------------------|
+//! | [`RomuQuad`]    | 4 × `u64` | 2^90             |
+//! | [`RomuTrio`]    | 3 × `u64` | 2^75             |
+//! | [`RomuDuo`]     | 2 × `u64` | 2^61             |
+//! | [`RomuDuoJr`]   | 2 × `u64` | 2^51             |
+//! | [`RomuQuad32`]  | 4 × `u32` | 2^62             |
+//! | [`RomuTrio32`]  | 3 × `u32` | 2^53             |
+//!
+//! The paper defines no seeding routine, only that the state must not be
+//! all zero. `new(seed)` fills the state words with successive outputs of
+//! [`SplitMix64`], as `xorshiro` does. `from_words` sets the state directly,
+//! and with the same state words the raw output matches the reference code.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tc_prng::romu::RomuDuoJr;
+//!
+//! let mut rng = RomuDuoJr::from_words([1, 2]);
+//! assert_eq!(rng.next::<u64>(), 1);
+//! assert_eq!(rng.next::<u64>(), 12035444495808507542);
+//! ```
+
+use crate::{
+    split_mix::SplitMix64,
+    state::{Algorithm, Codec, Persist, Reader, StateError},
+    Prng, PrngCore, Seedable,
+};
+
+/// RomuQuad, the most robust of the family.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RomuQuad {
+    s: [u64; 4],
+}
+
+/// RomuTrio, the recommended general purpose member.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RomuTrio {
+    s: [u64; 3],
+}
+
+/// RomuDuo.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RomuDuo {
+    s: [u64; 2],
+}
+
+/// RomuDuoJr, the fastest member, for small jobs.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RomuDuoJr {
+    s: [u64; 2],
+}
+
+/// RomuQuad with 32-bit words.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RomuQuad32 {
+    s: [u32; 4],
+}
+
+/// RomuTrio with 32-bit words.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RomuTrio32 {
+    s: [u32; 3],
+}
+
+const MUL64: u64 = 15241094284759029579;
+const MUL32: u32 = 3323815723;
+
+impl PrngCore for RomuQuad {
+    type Word = u64;
+
+    fn calc(&mut self) -> u64 {
+        let [w, x, y, z] = self.s;
+        self.s = [
+            MUL64.wrapping_mul(z),
+            z.wrapping_add(w.rotate_left(52)),
+            y.wrapping_sub(x),
+            y.wrapping_add(w).rotate_left(19),
+        ];
+        x
+    }
+}
+
+impl PrngCore for RomuTrio {
+    type Word = u64;
+
+    fn calc(&mut self) -> u64 {
+        let [x, y, z] = self.s;
+        self.s = [
+            MUL64.wrapping_mul(z),
+            y.wrapping_sub(x).rotate_left(12),
+            z.wrapping_sub(y).rotate_left(44),
+        ];
+        x
+    }
+}
+
+impl PrngCore for RomuDuo {
+    type Word = u64;
+
+    fn calc(&mut self) -> u64 {
+        let [x, y] = self.s;
+        self.s = [
+            MUL64.wrapping_mul(y),
+            y.rotate_left(36)
+                .wrapping_add(y.rotate_left(15))
+                .wrapping_sub(x),
+        ];
+        x
+    }
+}
+
+impl PrngCore for RomuDuoJr {
+    type Word = u64;
+
+    fn calc(&mut self) -> u64 {
+        let [x, y] = self.s;
+        self.s = [MUL64.wrapping_mul(y), y.wrapping_sub(x).rotate_left(27)];
+        x
+    }
+}
+
+impl PrngCore for RomuQuad32 {
+    type Word = u32;
+
+    fn calc(&mut self) -> u32 {
+        let [w, x, y, z] = self.s;
+        self.s = [
+            MUL32.wrapping_mul(z),
+            z.wrapping_add(w.rotate_left(26)),
+            y.wrapping_sub(x),
+            y.wrapping_add(w).rotate_left(9),
+        ];
+        x
+    }
+}
+
+impl PrngCore for RomuTrio32 {
+    type Word = u32;
+
+    fn calc(&mut self) -> u32 {
+        let [x, y, z] = self.s;
+        self.s = [
+            MUL32.wrapping_mul(z),
+            y.wrapping_sub(x).rotate_left(6),
+            z.wrapping_sub(y).rotate_left(22),
+        ];
+        x
+    }
+}
+
+macro_rules! impl_romu {
+    ($($ty:ident: [$word:ty; $n:literal], $alg:ident, $bits:literal, $read:ident;)*) => {$(
+        impl $ty {
+            pub fn new(seed: u64) -> Self {
+                let mut seeder = SplitMix64::new(seed);
+                Self {
+                    s: std::array::from_fn(|_| seeder.next::<$word>()),
+                }
+            }
+
+            /// Uses `words` as the state, in the order of the reference code.
+            ///
+            /// # Panics
+            ///
+            /// If every word is zero, the one state that never leaves zero.
+            pub fn from_words(words: [$word; $n]) -> Self {
+                assert!(words.iter().any(|w| *w != 0), "state must not be all zero");
+                Self { s: words }
+            }
+
+            #[allow(clippy::should_implement_trait)]
+            pub fn next<V>(&mut self) -> V
+            where
+                Self: Prng<V>,
+            {
+                Prng::next_val(self)
+            }
+        }
+
+        impl Default for $ty {
+            fn default() -> Self {
+                Self::new(0)
+            }
+        }
+
+        impl Seedable for $ty {
+            type Seed = [u8; 8];
+
+            fn from_seed(seed: Self::Seed) -> Self {
+                Self::new(u64::from_le_bytes(seed))
+            }
+        }
+
+        impl Persist for $ty {
+            type State = [$word; $n];
+
+            const ALGORITHM: Algorithm = Algorithm::$alg;
+            const VARIANT: u8 = $bits;
+            const STATE_LEN: usize = $n * $bits / 8;
+
+            fn state(&self) -> Self::State {
+                self.s
+            }
+
+            fn from_state(state: Self::State) -> Self {
+                Self { s: state }
+            }
+        }
+
+        impl Codec for $ty {
+            fn encode(&self, out: &mut Vec<u8>) {
+                for w in self.s {
+                    out.extend_from_slice(&w.to_le_bytes());
+                }
+            }
+
+            fn decode(bytes: &[u8]) -> Result<Self, StateError> {
+                let mut r = Reader(bytes);
+                Ok(Self::from_state(std::array::from_fn(|_| r.$read())))
+            }
+        }
+    )*};
+}
+
+impl_romu! {
+    RomuQuad: [u64; 4], RomuQuad, 64, u64;
+    RomuTrio: [u64; 3], RomuTrio, 64, u64;
+    RomuDuo: [u64; 2], RomuDuo, 64, u64;
+    RomuDuoJr: [u64; 2], RomuDuoJr, 64, u64;
+    RomuQuad32: [u32; 4], RomuQuad, 32, u32;
+    RomuTrio32: [u32; 3], RomuTrio, 32, u32;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Reference values from Overton's `romu.c`, started from the same words.
+
+    fn first_and_1000th<G: PrngCore>(mut rng: G) -> (Vec<G::Word>, G::Word) {
+        let first = (0..3).map(|_| rng.calc()).collect();
+        let v = (3..1000).map(|_| rng.calc()).last().unwrap();
+        (first, v)
+    }
+
+    #[test]
+    fn reference_vectors() {
+        let (first, last) = first_and_1000th(RomuQuad::from_words([1, 2, 3, 4]));
+        assert_eq!(first, [2, 4503599627370500, 15187511025750758165]);
+        assert_eq!(last, 11696813450902866858);
+
+        let (first, last) = first_and_1000th(RomuTrio::from_words([1, 2, 3]));
+        assert_eq!(first, [1, 8829794706857985505, 14228190636816728064]);
+        assert_eq!(last, 3966642466338003095);
+
+        let (first, last) = first_and_1000th(RomuDuo::from_words([1, 2]));
+        assert_eq!(first, [1, 12035444495808507542, 6091112088061520053]);
+        assert_eq!(last, 2483430981891349939);
+
+        let (first, last) = first_and_1000th(RomuDuoJr::from_words([1, 2]));
+        assert_eq!(first, [1, 12035444495808507542, 178563687714390016]);
+        assert_eq!(last, 17868408720384553113);
+
+        let (first, last) = first_and_1000th(RomuQuad32::from_words([1, 2, 3, 4]));
+        assert_eq!(first, [2, 67108868, 2959203954]);
+        assert_eq!(last, 3053058570);
+
+        let (first, last) = first_and_1000th(RomuTrio32::from_words([1, 2, 3]));
+        assert_eq!(first, [1, 1381512577, 3401580544]);
+        assert_eq!(last, 3054640024);
+    }
+
+    #[test]
+    #[should_panic(expected = "state must not be all zero")]
+    fn zero_state_is_rejected() {
+        RomuTrio::from_words([0; 3]);
+    }
+
+    #[test]
+    fn dump_value_types() {
+        let mut rng = RomuTrio::new(123456);
+
+        let a: u8 = rng.next();
+        assert_eq!(a, 230);
+
+        let b: u128 = rng.next();
+        assert_eq!(b, 34796674573773305753666506207173640727);
+
+        let c: bool = rng.next();
+        assert!(!c);
+
+        let d: f32 = rng.next();
+        assert_eq!(d, 0.44942683);
+    }
+}
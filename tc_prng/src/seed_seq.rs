@@ -0,0 +1,287 @@
+//! Hierarchical seed derivation.
+//!
+//! [`SeedSequence`] implements the same hashing and mixing as NumPy's
+//! `SeedSequence` (itself based on Melissa O'Neill's `seed_seq_fe`): the
+//! root entropy and a path of spawn keys are folded into a small pool, from
+//! which any number of well-mixed words can be drawn. The algorithm is part
+//! of the crate's output contract and does not depend on the platform.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tc_prng::{prelude::*, seed_seq::SeedSequence};
+//!
+//! let campaign = SeedSequence::new(0xC0FFEE);
+//! let level_3 = campaign.child(3);
+//! let boss: Xorshiro64 = level_3.child(7).seed();
+//!
+//! // Or straight from a label.
+//! let boss = Xorshiro64::from_seed_str("level-3/boss");
+//! ```
+
+use crate::Seedable;
+
+const POOL_SIZE: usize = 4;
+const INIT_A: u32 = 0x43b0d7e5;
+const MULT_A: u32 = 0x931e8875;
+const INIT_B: u32 = 0x8b51f9dd;
+const MULT_B: u32 = 0x58f38ded;
+const MIX_MULT_L: u32 = 0xca01f9dd;
+const MIX_MULT_R: u32 = 0x4973f715;
+const XSHIFT: u32 = 16;
+
+/// Root entropy plus a spawn key path, hashed into a pool of seed words.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SeedSequence {
+    entropy: Vec<u32>,
+    spawn_key: Vec<u32>,
+    pool: [u32; POOL_SIZE],
+    spawned: u32,
+}
+
+impl SeedSequence {
+    /// A root sequence from an integer seed.
+    pub fn new(seed: u128) -> Self {
+        Self::from_words(&int_words(seed), &[])
+    }
+
+    /// A sequence from raw entropy words and a spawn key path.
+    pub fn from_words(entropy: &[u32], spawn_key: &[u32]) -> Self {
+        let mut seq = Self {
+            entropy: entropy.to_vec(),
+            spawn_key: spawn_key.to_vec(),
+            pool: [0; POOL_SIZE],
+            spawned: 0,
+        };
+        seq.mix_entropy();
+        seq
+    }
+
+    /// A root sequence from arbitrary bytes.
+    ///
+    /// The bytes are read as little-endian words, zero padded, followed by
+    /// the byte length so that trailing zero bytes still change the result.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let mut words: Vec<u32> = bytes
+            .chunks(4)
+            .map(|c| {
+                let mut w = [0; 4];
+                w[..c.len()].copy_from_slice(c);
+                u32::from_le_bytes(w)
+            })
+            .collect();
+        words.push(bytes.len() as u32);
+        Self::from_words(&words, &[])
+    }
+
+    pub fn entropy(&self) -> &[u32] {
+        &self.entropy
+    }
+
+    pub fn spawn_key(&self) -> &[u32] {
+        &self.spawn_key
+    }
+
+    /// The child at `key` below this sequence.
+    pub fn child(&self, key: u32) -> Self {
+        self.path(&[key])
+    }
+
+    /// The descendant reached by following `keys` below this sequence.
+    pub fn path(&self, keys: &[u32]) -> Self {
+        let mut spawn_key = self.spawn_key.clone();
+        spawn_key.extend_from_slice(keys);
+        Self::from_words(&self.entropy, &spawn_key)
+    }
+
+    /// The next `n` children, continuing after any spawned before.
+    ///
+    /// # Panics
+    ///
+    /// If this would spawn more than `u32::MAX` children in total, since
+    /// child keys are `u32`. Use [`child`](Self::child) to reach any key
+    /// directly.
+    pub fn spawn(&mut self, n: u32) -> Vec<Self> {
+        let start = self.spawned;
+        self.spawned = start
+            .checked_add(n)
+            .expect("spawned more than u32::MAX children");
+        (start..self.spawned).map(|k| self.child(k)).collect()
+    }
+
+    /// `n` 32-bit seed words.
+    pub fn generate_u32(&self, n: usize) -> Vec<u32> {
+        let mut out = vec![0; n];
+        self.fill_u32(&mut out);
+        out
+    }
+
+    /// `n` 64-bit seed words, each made of two 32-bit words, low first.
+    pub fn generate_u64(&self, n: usize) -> Vec<u64> {
+        self.generate_u32(n * 2)
+            .chunks_exact(2)
+            .map(|w| (w[1] as u64) << 32 | w[0] as u64)
+            .collect()
+    }
+
+    pub fn fill_u32(&self, dst: &mut [u32]) {
+        let mut hash_const = INIT_B;
+        for (d, v) in dst.iter_mut().zip(self.pool.iter().cycle()) {
+            let mut v = *v ^ hash_const;
+            hash_const = hash_const.wrapping_mul(MULT_B);
+            v = v.wrapping_mul(hash_const);
+            *d = v ^ (v >> XSHIFT);
+        }
+    }
+
+    /// Fills `dst` with the little-endian bytes of the seed words.
+    pub fn fill_bytes(&self, dst: &mut [u8]) {
+        let words = self.generate_u32(dst.len().div_ceil(4));
+        for (c, w) in dst.chunks_mut(4).zip(words) {
+            c.copy_from_slice(&w.to_le_bytes()[..c.len()]);
+        }
+    }
+
+    /// Seeds any generator from this sequence.
+    pub fn seed<G: Seedable>(&self) -> G {
+        G::from_seed_sequence(self)
+    }
+
+    fn assembled_entropy(&self) -> Vec<u32> {
+        let mut words = self.entropy.clone();
+        if !self.spawn_key.is_empty() && words.len() < POOL_SIZE {
+            // Pad so spawn keys can never alias plain entropy words.
+            words.resize(POOL_SIZE, 0);
+        }
+        words.extend_from_slice(&self.spawn_key);
+        words
+    }
+
+    fn mix_entropy(&mut self) {
+        let entropy = self.assembled_entropy();
+        let mut hash_const = INIT_A;
+        let mixer = &mut self.pool;
+
+        for (i, m) in mixer.iter_mut().enumerate() {
+            *m = hashmix(entropy.get(i).copied().unwrap_or(0), &mut hash_const);
+        }
+        for src in 0..POOL_SIZE {
+            for dst in 0..POOL_SIZE {
+                if src != dst {
+                    let h = hashmix(mixer[src], &mut hash_const);
+                    mixer[dst] = mix(mixer[dst], h);
+                }
+            }
+        }
+        for e in entropy.iter().skip(POOL_SIZE) {
+            for m in mixer.iter_mut() {
+                *m = mix(*m, hashmix(*e, &mut hash_const));
+            }
+        }
+    }
+}
+
+impl From<&[u8]> for SeedSequence {
+    fn from(bytes: &[u8]) -> Self {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl From<&str> for SeedSequence {
+    fn from(s: &str) -> Self {
+        Self::from_bytes(s.as_bytes())
+    }
+}
+
+/// Splits an integer into 32-bit words, least significant first, keeping at
+/// least one word.
+fn int_words(mut v: u128) -> Vec<u32> {
+    let mut words = vec![v as u32];
+    v >>= 32;
+    while v != 0 {
+        words.push(v as u32);
+        v >>= 32;
+    }
+    words
+}
+
+fn hashmix(value: u32, hash_const: &mut u32) -> u32 {
+    let mut v = value ^ *hash_const;
+    *hash_const = hash_const.wrapping_mul(MULT_A);
+    v = v.wrapping_mul(*hash_const);
+    v ^ (v >> XSHIFT)
+}
+
+fn mix(x: u32, y: u32) -> u32 {
+    let r = MIX_MULT_L
+        .wrapping_mul(x)
+        .wrapping_sub(MIX_MULT_R.wrapping_mul(y));
+    r ^ (r >> XSHIFT)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn seed_seq_fe_reference() {
+        // Reference data shared with NumPy's test suite.
+        let seq = SeedSequence::from_words(&[3735928559, 195939070, 229505742, 305419896], &[]);
+        assert_eq!(
+            seq.generate_u32(4),
+            [3914649087, 576849849, 3593928901, 2229911004]
+        );
+    }
+
+    #[test]
+    fn paths_are_stable_and_distinct() {
+        let root = SeedSequence::new(42);
+        assert_eq!(root.child(1).child(2), root.path(&[1, 2]));
+        assert_ne!(root.child(1).generate_u32(4), root.child(2).generate_u32(4));
+        assert_ne!(root.generate_u32(4), root.child(0).generate_u32(4));
+
+        let mut spawner = root.clone();
+        let kids = spawner.spawn(2);
+        assert_eq!(kids[1], root.child(1));
+        assert_eq!(spawner.spawn(1)[0], root.child(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "spawned more than u32::MAX children")]
+    fn spawn_overflow() {
+        let mut spawner = SeedSequence::new(42);
+        spawner.spawned = u32::MAX - 1;
+        assert_eq!(spawner.spawn(1), [spawner.child(u32::MAX - 1)]);
+        spawner.spawn(1);
+    }
+
+    #[test]
+    fn bytes_and_strings() {
+        assert_eq!(
+            SeedSequence::from("level-3/boss"),
+            SeedSequence::from_bytes(b"level-3/boss")
+        );
+        assert_ne!(
+            SeedSequence::from_bytes(b"ab").pool,
+            SeedSequence::from_bytes(b"ab\0").pool
+        );
+
+        let mut a = Xorshiro64::from_seed_str("level-3/boss");
+        let mut b: Xorshiro64 = SeedSequence::from("level-3/boss").seed();
+        assert_eq!(a.next::<u64>(), b.next::<u64>());
+    }
+
+    #[test]
+    fn dump_value_types() {
+        let seq = SeedSequence::new(123456);
+        let a = seq.generate_u32(2);
+        assert_eq!(a, [3446179238, 3086685416]);
+
+        let b = seq.child(5).generate_u64(2);
+        assert_eq!(b, [7633496370941834573, 4411386462864097317]);
+
+        let c = SeedSequence::from("level-3/boss").generate_u32(2);
+        assert_eq!(c, [684945619, 4125806490]);
+    }
+}
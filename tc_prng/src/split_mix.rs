@@ -1,4 +1,7 @@
-use crate::{prng_128 as p128, prng_32 as p32, prng_64 as p64};
+use crate::{
+    state::{Algorithm, Codec, Persist, Reader, StateError},
+    Prng, PrngCore, Seedable,
+};
 
 pub fn split_mix<T>(seed: T) -> SplitMix<T> {
     SplitMix::new(seed)
@@ -27,6 +30,108 @@ impl Default for SplitMix<u128> {
     }
 }
 
+impl Seedable for SplitMix<u32> {
+    type Seed = [u8; 4];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(u32::from_le_bytes(seed))
+    }
+}
+
+impl Seedable for SplitMix<u64> {
+    type Seed = [u8; 8];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(u64::from_le_bytes(seed))
+    }
+}
+
+impl Seedable for SplitMix<u128> {
+    type Seed = [u8; 16];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(u128::from_le_bytes(seed))
+    }
+}
+
+impl Persist for SplitMix<u32> {
+    type State = u32;
+
+    const ALGORITHM: Algorithm = Algorithm::SplitMix;
+    const VARIANT: u8 = 32;
+    const STATE_LEN: usize = 4;
+
+    fn state(&self) -> u32 {
+        self.state
+    }
+
+    fn from_state(state: u32) -> Self {
+        Self { state }
+    }
+}
+
+impl Codec for SplitMix<u32> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.state.to_le_bytes());
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, StateError> {
+        Ok(Self::from_state(Reader(bytes).u32()))
+    }
+}
+
+impl Persist for SplitMix<u64> {
+    type State = u64;
+
+    const ALGORITHM: Algorithm = Algorithm::SplitMix;
+    const VARIANT: u8 = 64;
+    const STATE_LEN: usize = 8;
+
+    fn state(&self) -> u64 {
+        self.state
+    }
+
+    fn from_state(state: u64) -> Self {
+        Self { state }
+    }
+}
+
+impl Codec for SplitMix<u64> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.state.to_le_bytes());
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, StateError> {
+        Ok(Self::from_state(Reader(bytes).u64()))
+    }
+}
+
+impl Persist for SplitMix<u128> {
+    type State = u128;
+
+    const ALGORITHM: Algorithm = Algorithm::SplitMix;
+    const VARIANT: u8 = 128;
+    const STATE_LEN: usize = 16;
+
+    fn state(&self) -> u128 {
+        self.state
+    }
+
+    fn from_state(state: u128) -> Self {
+        Self { state }
+    }
+}
+
+impl Codec for SplitMix<u128> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.state.to_le_bytes());
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, StateError> {
+        Ok(Self::from_state(Reader(bytes).u128()))
+    }
+}
+
 impl<T> SplitMix<T> {
     pub fn new(seed: T) -> Self {
         Self { state: seed }
@@ -34,80 +139,134 @@ impl<T> SplitMix<T> {
 }
 
 impl SplitMix<u32> {
-    const ADD: u32 = 0x9e3779b9;
+    pub(crate) const ADD: u32 = 0x9e3779b9;
     const MU1: u32 = 0xbf58476d;
     const MU2: u32 = 0x94d049bb;
     const SH1: usize = 15;
     const SH2: usize = 13;
     const SH3: usize = 16;
 
+    #[allow(clippy::should_implement_trait)]
     pub fn next<V>(&mut self) -> V
     where
-        Self: p32::Prng<V>,
+        Self: Prng<V>,
     {
-        p32::Prng::next_val(self)
+        Prng::next_val(self)
+    }
+
+    /// Moves `n` steps forward in O(1), as if `n` words had been drawn.
+    pub fn advance(&mut self, n: u128) {
+        self.state = self.state.wrapping_add(Self::ADD.wrapping_mul(n as u32));
+    }
+
+    /// Moves `n` steps back in O(1), undoing `n` draws.
+    pub fn rewind(&mut self, n: u128) {
+        self.state = self.state.wrapping_sub(Self::ADD.wrapping_mul(n as u32));
+    }
+
+    /// The bijective finalizer applied to every output. On its own it is a
+    /// good hash of a single word.
+    pub fn mix(z: u32) -> u32 {
+        let z = (z ^ (z >> Self::SH1)).wrapping_mul(Self::MU1);
+        let z = (z ^ (z >> Self::SH2)).wrapping_mul(Self::MU2);
+        z ^ (z >> Self::SH3)
     }
 }
 
 impl SplitMix<u64> {
-    const ADD: u64 = 0x9e3779b9_7f4a7c15;
+    pub(crate) const ADD: u64 = 0x9e3779b9_7f4a7c15;
     const MU1: u64 = 0xbf58476d_1ce4e5b9;
     const MU2: u64 = 0x94d049bb_133111eb;
     const SH1: usize = 30;
     const SH2: usize = 27;
     const SH3: usize = 31;
 
+    #[allow(clippy::should_implement_trait)]
     pub fn next<V>(&mut self) -> V
     where
-        Self: p64::Prng<V>,
+        Self: Prng<V>,
     {
-        p64::Prng::next_val(self)
+        Prng::next_val(self)
+    }
+
+    /// Moves `n` steps forward in O(1), as if `n` words had been drawn.
+    pub fn advance(&mut self, n: u128) {
+        self.state = self.state.wrapping_add(Self::ADD.wrapping_mul(n as u64));
+    }
+
+    /// Moves `n` steps back in O(1), undoing `n` draws.
+    pub fn rewind(&mut self, n: u128) {
+        self.state = self.state.wrapping_sub(Self::ADD.wrapping_mul(n as u64));
+    }
+
+    /// The bijective finalizer applied to every output. On its own it is a
+    /// good hash of a single word.
+    pub fn mix(z: u64) -> u64 {
+        let z = (z ^ (z >> Self::SH1)).wrapping_mul(Self::MU1);
+        let z = (z ^ (z >> Self::SH2)).wrapping_mul(Self::MU2);
+        z ^ (z >> Self::SH3)
     }
 }
 
 impl SplitMix<u128> {
-    const ADD: u128 = 0x9e3779b9_7f4a7c15_243f6a88;
+    pub(crate) const ADD: u128 = 0x9e3779b9_7f4a7c15_243f6a88;
     const MU1: u128 = 0xbf58476d_1ce4e5b9_b7e15162;
     const MU2: u128 = 0x94d049bb_133111eb_9e3779b9;
     const SH1: usize = 60;
     const SH2: usize = 54;
     const SH3: usize = 62;
 
+    #[allow(clippy::should_implement_trait)]
     pub fn next<V>(&mut self) -> V
     where
-        Self: p128::Prng<V>,
+        Self: Prng<V>,
     {
-        p128::Prng::next_val(self)
+        Prng::next_val(self)
     }
-}
 
-impl p32::Prng32 for SplitMix<u32> {
-    fn calc(&mut self) -> u32 {
-        self.state = self.state.wrapping_add(Self::ADD);
-        let z = self.state;
+    /// Moves `n` steps forward in O(1), as if `n` words had been drawn.
+    pub fn advance(&mut self, n: u128) {
+        self.state = self.state.wrapping_add(Self::ADD.wrapping_mul(n));
+    }
+
+    /// Moves `n` steps back in O(1), undoing `n` draws.
+    pub fn rewind(&mut self, n: u128) {
+        self.state = self.state.wrapping_sub(Self::ADD.wrapping_mul(n));
+    }
+
+    /// The bijective finalizer applied to every output. On its own it is a
+    /// good hash of a single word.
+    pub fn mix(z: u128) -> u128 {
         let z = (z ^ (z >> Self::SH1)).wrapping_mul(Self::MU1);
         let z = (z ^ (z >> Self::SH2)).wrapping_mul(Self::MU2);
         z ^ (z >> Self::SH3)
     }
 }
 
-impl p64::Prng64 for SplitMix<u64> {
+impl PrngCore for SplitMix<u32> {
+    type Word = u32;
+
+    fn calc(&mut self) -> u32 {
+        self.state = self.state.wrapping_add(Self::ADD);
+        Self::mix(self.state)
+    }
+}
+
+impl PrngCore for SplitMix<u64> {
+    type Word = u64;
+
     fn calc(&mut self) -> u64 {
         self.state = self.state.wrapping_add(Self::ADD);
-        let z = self.state;
-        let z = (z ^ (z >> Self::SH1)).wrapping_mul(Self::MU1);
-        let z = (z ^ (z >> Self::SH2)).wrapping_mul(Self::MU2);
-        z ^ (z >> Self::SH3)
+        Self::mix(self.state)
     }
 }
 
-impl p128::Prng128 for SplitMix<u128> {
+impl PrngCore for SplitMix<u128> {
+    type Word = u128;
+
     fn calc(&mut self) -> u128 {
         self.state = self.state.wrapping_add(Self::ADD);
-        let z = self.state;
-        let z = (z ^ (z >> Self::SH1)).wrapping_mul(Self::MU1);
-        let z = (z ^ (z >> Self::SH2)).wrapping_mul(Self::MU2);
-        z ^ (z >> Self::SH3)
+        Self::mix(self.state)
     }
 }
 
@@ -120,6 +279,32 @@ mod test {
     use super::*;
 
     #[test]
+    fn advance_and_rewind() {
+        let mut a = split_mix(77_u32);
+        let mut b = a;
+        for _ in 0..10 {
+            a.calc();
+        }
+        b.advance(10);
+        assert!(a == b);
+        b.rewind(10);
+        assert_eq!(b.next::<u32>(), split_mix(77_u32).next::<u32>());
+
+        let mut c = split_mix(5_u128);
+        let start = c;
+        c.advance(u128::MAX);
+        c.rewind(u128::MAX);
+        assert!(c == start);
+
+        let mut d = split_mix(9_u64);
+        let first: u64 = d.next();
+        d.next::<u64>();
+        d.rewind(2);
+        assert_eq!(d.next::<u64>(), first);
+    }
+
+    #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn dump_value_types() {
         let mut rng = SplitMix::new(123456_u64);
 
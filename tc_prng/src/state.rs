@@ -0,0 +1,321 @@
+//! Versioned generator state for save games.
+//!
+//! [`Persist::to_bytes`] writes a 5 byte header followed by the generator's
+//! state, every integer little-endian:
+//!
+//! | offset | size | content                                         |
+//! |--------|------|-------------------------------------------------|
+//! | 0      | 2    | magic `b"tc"`                                   |
+//! | 2      | 1    | format version, currently `1`                   |
+//! | 3      | 1    | [`Algorithm`] id                                |
+//! | 4      | 1    | variant: word width in bits, or ChaCha rounds   |
+//! | 5      | n    | algorithm specific state                        |
+//!
+//! Restoring continues the exact sequence the generator would have produced.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tc_prng::{prelude::*, state::Persist};
+//!
+//! let mut rng = xorshiro(42_u64);
+//! let _: u32 = rng.next();
+//!
+//! let save = rng.to_bytes();
+//! let mut restored = Xorshiro64::from_bytes(&save).unwrap();
+//! assert_eq!(rng.next::<u64>(), restored.next::<u64>());
+//!
+//! assert!(SplitMix64::from_bytes(&save).is_err());
+//! ```
+
+use std::fmt;
+
+pub const MAGIC: [u8; 2] = *b"tc";
+pub const VERSION: u8 = 1;
+const HEADER_LEN: usize = 5;
+
+/// Generator family stored in the header.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Algorithm {
+    SplitMix = 1,
+    Xorshiro = 2,
+    ChaCha = 3,
+    Philox = 4,
+    Threefry = 5,
+    Mt19937 = 6,
+    MinStdRand0 = 7,
+    MinStdRand = 8,
+    Drand48 = 9,
+    GlibcRand = 10,
+    AnsiCRand = 11,
+    WyRand = 12,
+    Sfc = 13,
+    Jsf = 14,
+    RomuQuad = 15,
+    RomuTrio = 16,
+    RomuDuo = 17,
+    RomuDuoJr = 18,
+}
+
+impl Algorithm {
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(Algorithm::SplitMix),
+            2 => Some(Algorithm::Xorshiro),
+            3 => Some(Algorithm::ChaCha),
+            4 => Some(Algorithm::Philox),
+            5 => Some(Algorithm::Threefry),
+            6 => Some(Algorithm::Mt19937),
+            7 => Some(Algorithm::MinStdRand0),
+            8 => Some(Algorithm::MinStdRand),
+            9 => Some(Algorithm::Drand48),
+            10 => Some(Algorithm::GlibcRand),
+            11 => Some(Algorithm::AnsiCRand),
+            12 => Some(Algorithm::WyRand),
+            13 => Some(Algorithm::Sfc),
+            14 => Some(Algorithm::Jsf),
+            15 => Some(Algorithm::RomuQuad),
+            16 => Some(Algorithm::RomuTrio),
+            17 => Some(Algorithm::RomuDuo),
+            18 => Some(Algorithm::RomuDuoJr),
+            _ => None,
+        }
+    }
+}
+
+/// Why a serialized state could not be restored.
+#[derive(Debug, PartialEq, Eq)]
+pub enum StateError {
+    /// The data does not start with [`MAGIC`].
+    BadMagic,
+    /// The data was written by an unknown format version.
+    UnsupportedVersion(u8),
+    /// The data holds another generator or another width of this one.
+    WrongGenerator {
+        expected: (Algorithm, u8),
+        found: (u8, u8),
+    },
+    /// The state length does not match the generator.
+    Length { expected: usize, found: usize },
+    /// The state can never occur in the generator, like an all-zero
+    /// xorshift state.
+    Invalid,
+}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StateError::BadMagic => write!(f, "not a tc_prng state"),
+            StateError::UnsupportedVersion(v) => write!(f, "unsupported state version {v}"),
+            StateError::WrongGenerator { expected, found } => {
+                let name = Algorithm::from_id(found.0)
+                    .map(|a| format!("{a:?}"))
+                    .unwrap_or_else(|| format!("unknown algorithm {}", found.0));
+                write!(
+                    f,
+                    "state is for {name}/{}, expected {:?}/{}",
+                    found.1, expected.0, expected.1
+                )
+            }
+            StateError::Length { expected, found } => {
+                write!(f, "state is {found} bytes, expected {expected}")
+            }
+            StateError::Invalid => write!(f, "state cannot occur in the generator"),
+        }
+    }
+}
+
+impl std::error::Error for StateError {}
+
+mod sealed {
+    use super::StateError;
+
+    /// The byte encoding behind [`Persist`](super::Persist). Kept out of
+    /// reach so only `from_bytes`, after checking the header and length,
+    /// decodes.
+    pub trait Codec: Sized {
+        /// Appends the little-endian encoding of the state.
+        fn encode(&self, out: &mut Vec<u8>);
+
+        /// Decodes a state of exactly `STATE_LEN` bytes.
+        fn decode(bytes: &[u8]) -> Result<Self, StateError>;
+    }
+}
+
+pub(crate) use sealed::Codec;
+
+/// Generators whose state can be inspected, saved and restored.
+pub trait Persist: Codec {
+    /// Everything needed to resume the generator.
+    type State;
+
+    const ALGORITHM: Algorithm;
+    /// Word width in bits, or the round count for ChaCha.
+    const VARIANT: u8;
+    /// Size of the encoded state, without the header.
+    const STATE_LEN: usize;
+
+    fn state(&self) -> Self::State;
+
+    fn from_state(state: Self::State) -> Self;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(HEADER_LEN + Self::STATE_LEN);
+        out.extend_from_slice(&MAGIC);
+        out.push(VERSION);
+        out.push(Self::ALGORITHM as u8);
+        out.push(Self::VARIANT);
+        self.encode(&mut out);
+        out
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, StateError> {
+        if bytes.len() < HEADER_LEN || bytes[..2] != MAGIC {
+            return Err(StateError::BadMagic);
+        }
+        if bytes[2] != VERSION {
+            return Err(StateError::UnsupportedVersion(bytes[2]));
+        }
+        if bytes[3] != Self::ALGORITHM as u8 || bytes[4] != Self::VARIANT {
+            return Err(StateError::WrongGenerator {
+                expected: (Self::ALGORITHM, Self::VARIANT),
+                found: (bytes[3], bytes[4]),
+            });
+        }
+        let state = &bytes[HEADER_LEN..];
+        if state.len() != Self::STATE_LEN {
+            return Err(StateError::Length {
+                expected: Self::STATE_LEN,
+                found: state.len(),
+            });
+        }
+        Self::decode(state)
+    }
+}
+
+/// Reads consecutive little-endian integers from a state.
+pub(crate) struct Reader<'a>(pub(crate) &'a [u8]);
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> [u8; N] {
+        let (head, rest) = self.0.split_at(N);
+        self.0 = rest;
+        head.try_into().unwrap()
+    }
+
+    pub(crate) fn u32(&mut self) -> u32 {
+        u32::from_le_bytes(self.take())
+    }
+
+    pub(crate) fn u64(&mut self) -> u64 {
+        u64::from_le_bytes(self.take())
+    }
+
+    pub(crate) fn u128(&mut self) -> u128 {
+        u128::from_le_bytes(self.take())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        chacha::ChaCha12,
+        jsf::{Jsf32, Jsf64},
+        philox::Philox4x32,
+        prelude::*,
+        romu::{RomuDuo, RomuDuoJr, RomuQuad, RomuQuad32, RomuTrio, RomuTrio32},
+        sfc::{Sfc32, Sfc64},
+        split_mix::SplitMix32,
+        threefry::Threefry2x64,
+        wyrand::WyRand,
+        xorshiro::Xorshiro128,
+    };
+
+    fn round_trip<G>(mut rng: G)
+    where
+        G: Persist + PrngCore,
+        G::Word: PartialEq + fmt::Debug,
+    {
+        for _ in 0..5 {
+            rng.calc();
+        }
+        let mut restored = G::from_bytes(&rng.to_bytes()).unwrap();
+        for _ in 0..50 {
+            assert_eq!(rng.calc(), restored.calc());
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        round_trip(split_mix(1_u32));
+        round_trip(split_mix(1_u64));
+        round_trip(split_mix(1_u128));
+        round_trip(xorshiro(1_u32));
+        round_trip(xorshiro(1_u64));
+        round_trip(xorshiro(1_u128));
+        round_trip(ChaCha12::with_stream([5; 32], 3));
+        round_trip(Philox4x32::new(1));
+        round_trip(Threefry2x64::new(1));
+        round_trip(WyRand::new(1));
+        round_trip(Sfc32::new(1));
+        round_trip(Sfc64::new(1));
+        round_trip(Jsf32::new(1));
+        round_trip(Jsf64::new(1));
+        round_trip(RomuQuad::new(1));
+        round_trip(RomuTrio::new(1));
+        round_trip(RomuDuo::new(1));
+        round_trip(RomuDuoJr::new(1));
+        round_trip(RomuQuad32::new(1));
+        round_trip(RomuTrio32::new(1));
+    }
+
+    #[test]
+    fn state_round_trips() {
+        let mut rng = xorshiro(9_u128);
+        let _: u8 = rng.next();
+        let mut restored = Xorshiro128::from_state(rng.state());
+        assert_eq!(rng.next::<u128>(), restored.next::<u128>());
+    }
+
+    #[test]
+    fn header() {
+        let bytes = split_mix(0x0102_u64).to_bytes();
+        assert_eq!(bytes, [b't', b'c', 1, 1, 64, 2, 1, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn rejects_mismatches() {
+        let bytes = split_mix(7_u64).to_bytes();
+        assert_eq!(
+            SplitMix32::from_bytes(&bytes).err(),
+            Some(StateError::WrongGenerator {
+                expected: (Algorithm::SplitMix, 32),
+                found: (1, 64)
+            })
+        );
+        assert!(matches!(
+            Xorshiro64::from_bytes(&bytes).err(),
+            Some(StateError::WrongGenerator { .. })
+        ));
+        assert_eq!(
+            SplitMix64::from_bytes(&bytes[..bytes.len() - 1]).err(),
+            Some(StateError::Length {
+                expected: 8,
+                found: 7
+            })
+        );
+        assert_eq!(
+            SplitMix64::from_bytes(b"xx").err(),
+            Some(StateError::BadMagic)
+        );
+
+        let mut future = bytes.clone();
+        future[2] = 9;
+        assert_eq!(
+            SplitMix64::from_bytes(&future).err(),
+            Some(StateError::UnsupportedVersion(9))
+        );
+    }
+}
@@ -0,0 +1,133 @@
+//! A lazily seeded generator per thread.
+//!
+//! The first use on each thread seeds a [`Xorshiro64`] from
+//! [`entropy`](crate::entropy::entropy). [`random`] and [`random_range`]
+//! draw from it directly and [`thread_rng`] returns a handle to it for code
+//! that takes a generator. Tests can pin the current thread to a fixed
+//! sequence with [`seed_thread_rng`].
+//!
+//! The generator is fast but not cryptographically secure; use
+//! [`ChaCha20`](crate::chacha::ChaCha20) for anything players must not
+//! predict.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tc_prng::{random, random_range, seed_thread_rng, thread_rng};
+//!
+//! let volume: f32 = random();
+//! let roll = random_range(1..=20_u32);
+//!
+//! seed_thread_rng(42);
+//! let a: u64 = random();
+//! seed_thread_rng(42);
+//! assert_eq!(thread_rng().next::<u64>(), a);
+//! ```
+
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    range::SampleRange, xorshiro, xorshiro::Xorshiro64, FromWord, Prng, PrngCore, Seedable,
+};
+
+thread_local! {
+    static THREAD_RNG: Rc<RefCell<Xorshiro64>> = Rc::new(RefCell::new(
+        Xorshiro64::from_entropy().expect("could not seed the thread-local generator"),
+    ));
+}
+
+/// Handle to the current thread's generator.
+///
+/// Handles share the generator, so draws through any of them advance the
+/// same sequence. They cannot be sent to other threads.
+#[derive(Clone)]
+pub struct ThreadRng(Rc<RefCell<Xorshiro64>>);
+
+impl ThreadRng {
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<V>(&mut self) -> V
+    where
+        Self: Prng<V>,
+    {
+        Prng::next_val(self)
+    }
+}
+
+impl PrngCore for ThreadRng {
+    type Word = u64;
+
+    fn calc(&mut self) -> u64 {
+        self.0.borrow_mut().calc()
+    }
+}
+
+pub fn thread_rng() -> ThreadRng {
+    ThreadRng(THREAD_RNG.with(Rc::clone))
+}
+
+/// A value from the current thread's generator.
+pub fn random<T: FromWord<u64>>() -> T {
+    THREAD_RNG.with(|rng| rng.borrow_mut().next())
+}
+
+/// A value uniformly distributed over `range`, from the current thread's
+/// generator.
+pub fn random_range<T, R: SampleRange<T>>(range: R) -> T {
+    THREAD_RNG.with(|rng| range.sample(&mut *rng.borrow_mut()))
+}
+
+/// Replaces the current thread's generator with one seeded by `seed`.
+///
+/// Existing [`ThreadRng`] handles on this thread follow the new sequence.
+/// Other threads are unaffected.
+pub fn seed_thread_rng(seed: u64) {
+    set_thread_rng(xorshiro(seed));
+}
+
+/// Replaces the current thread's generator.
+pub fn set_thread_rng(rng: Xorshiro64) {
+    THREAD_RNG.with(|cell| *cell.borrow_mut() = rng);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn seeded_thread_is_reproducible() {
+        seed_thread_rng(99);
+        let mut handle = thread_rng();
+        let a: u64 = random();
+        let b: u64 = handle.next();
+        let c = random_range(0..1000_u32);
+
+        let mut expected = xorshiro(99_u64);
+        assert_eq!(a, expected.next::<u64>());
+        assert_eq!(b, expected.next::<u64>());
+        assert_eq!(c, expected.next_range(0..1000_u32));
+
+        // The handle follows a reseed.
+        seed_thread_rng(99);
+        assert_eq!(handle.next::<u64>(), a);
+    }
+
+    #[test]
+    fn threads_are_independent() {
+        seed_thread_rng(5);
+        let other: u64 = std::thread::spawn(|| {
+            seed_thread_rng(5);
+            let _: u64 = random();
+            random()
+        })
+        .join()
+        .unwrap();
+        let here: u64 = random();
+        assert_ne!(here, other);
+    }
+
+    #[test]
+    fn unseeded_threads_differ() {
+        let draw = || std::thread::spawn(random::<u64>).join().unwrap();
+        assert_ne!(draw(), draw());
+    }
+}
@@ -0,0 +1,230 @@
+use crate::{
+    state::{Algorithm, Codec, Persist, Reader, StateError},
+    Prng, PrngCore, Seedable,
+};
+
+/// Threefry2x64-20 counter-based generator (Salmon et al., Random123).
+///
+/// The Threefish-derived counterpart of [`Philox4x32`](crate::philox::Philox4x32):
+/// every 128-bit counter maps to a block of two 64-bit words, available in
+/// O(1) through [`at`](Self::at), while the sequential mode walks the
+/// counters from zero.
+///
+/// # Examples
+///
+/// ```rust
+/// use tc_prng::threefry::Threefry2x64;
+///
+/// // Block 2, words 4 and 5, of particle 17 in a stream keyed by the run
+/// // seed.
+/// let particle = 17_u128;
+/// let [w4, w5] = Threefry2x64::new(1234).at((particle << 64) | 2);
+/// ```
+#[derive(Copy, Clone, Ord, PartialOrd, PartialEq, Eq)]
+pub struct Threefry2x64 {
+    key: [u64; 2],
+    ctr: u128,
+    buf: [u64; 2],
+    idx: usize,
+}
+
+/// Position of a [`Threefry2x64`] generator, see [`Persist`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ThreefryState {
+    pub key: [u64; 2],
+    pub word_pos: u128,
+}
+
+impl Default for Threefry2x64 {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Seedable for Threefry2x64 {
+    type Seed = [u8; 16];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let k = u128::from_le_bytes(seed);
+        Self::from_key([k as u64, (k >> 64) as u64])
+    }
+}
+
+impl Persist for Threefry2x64 {
+    type State = ThreefryState;
+
+    const ALGORITHM: Algorithm = Algorithm::Threefry;
+    const VARIANT: u8 = 64;
+    const STATE_LEN: usize = 16 + 16;
+
+    fn state(&self) -> ThreefryState {
+        ThreefryState {
+            key: self.key,
+            word_pos: self.word_pos(),
+        }
+    }
+
+    fn from_state(state: ThreefryState) -> Self {
+        let mut rng = Self::from_key(state.key);
+        rng.seek(state.word_pos);
+        rng
+    }
+}
+
+impl Codec for Threefry2x64 {
+    fn encode(&self, out: &mut Vec<u8>) {
+        for k in self.key {
+            out.extend_from_slice(&k.to_le_bytes());
+        }
+        out.extend_from_slice(&self.word_pos().to_le_bytes());
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, StateError> {
+        let mut r = Reader(bytes);
+        Ok(Self::from_state(ThreefryState {
+            key: [r.u64(), r.u64()],
+            word_pos: r.u128(),
+        }))
+    }
+}
+
+impl Threefry2x64 {
+    const PARITY: u64 = 0x1BD11BDA_A9FC1A22;
+    const ROT: [u32; 8] = [16, 42, 12, 31, 16, 32, 24, 21];
+    const ROUNDS: usize = 20;
+
+    pub fn new(seed: u64) -> Self {
+        Self::from_key([seed, 0])
+    }
+
+    pub fn from_key(key: [u64; 2]) -> Self {
+        Self {
+            key,
+            ctr: 0,
+            buf: [0; 2],
+            idx: 2,
+        }
+    }
+
+    pub fn key(&self) -> [u64; 2] {
+        self.key
+    }
+
+    /// The block for `counter`. Counter words are taken little-endian.
+    pub fn at(&self, counter: u128) -> [u64; 2] {
+        Self::block([counter as u64, (counter >> 64) as u64], self.key)
+    }
+
+    /// Index of the next word of the sequential stream, wrapping at 2^128.
+    pub fn word_pos(&self) -> u128 {
+        // `ctr` already points past the buffered block.
+        self.ctr.wrapping_mul(2).wrapping_sub(2 - self.idx as u128)
+    }
+
+    /// Positions the sequential stream so the next word is word `n`.
+    pub fn seek(&mut self, n: u128) {
+        self.ctr = n / 2;
+        self.idx = (n % 2) as usize;
+        self.buf = self.at(self.ctr);
+        self.ctr = self.ctr.wrapping_add(1);
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<V>(&mut self) -> V
+    where
+        Self: Prng<V>,
+    {
+        Prng::next_val(self)
+    }
+
+    fn block(ctr: [u64; 2], key: [u64; 2]) -> [u64; 2] {
+        let ks = [key[0], key[1], Self::PARITY ^ key[0] ^ key[1]];
+        let mut x0 = ctr[0].wrapping_add(ks[0]);
+        let mut x1 = ctr[1].wrapping_add(ks[1]);
+
+        for r in 0..Self::ROUNDS {
+            x0 = x0.wrapping_add(x1);
+            x1 = x1.rotate_left(Self::ROT[r % 8]) ^ x0;
+
+            // Key injection after every 4th round.
+            if r % 4 == 3 {
+                let s = r / 4 + 1;
+                x0 = x0.wrapping_add(ks[s % 3]);
+                x1 = x1.wrapping_add(ks[(s + 1) % 3]).wrapping_add(s as u64);
+            }
+        }
+        [x0, x1]
+    }
+}
+
+impl PrngCore for Threefry2x64 {
+    type Word = u64;
+
+    fn calc(&mut self) -> u64 {
+        if self.idx == 2 {
+            self.buf = self.at(self.ctr);
+            self.ctr = self.ctr.wrapping_add(1);
+            self.idx = 0;
+        }
+        let v = self.buf[self.idx];
+        self.idx += 1;
+        v
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn kat(ctr: [u64; 2], key: [u64; 2]) -> [u64; 2] {
+        Threefry2x64::from_key(key).at(((ctr[1] as u128) << 64) | ctr[0] as u128)
+    }
+
+    #[test]
+    fn random123_known_answers() {
+        assert_eq!(
+            kat([0; 2], [0; 2]),
+            [0xc2b6e3a8c2c69865, 0x6f81ed42f350084d]
+        );
+        assert_eq!(
+            kat([u64::MAX; 2], [u64::MAX; 2]),
+            [0xe02cb7c4d95d277a, 0xd06633d0893b8b68]
+        );
+        assert_eq!(
+            kat(
+                [0x243f6a8885a308d3, 0x13198a2e03707344],
+                [0xa4093822299f31d0, 0x082efa98ec4e6c89]
+            ),
+            [0x263c7d30bb0f0af1, 0x56be8361d3311526]
+        );
+    }
+
+    #[test]
+    fn sequential_matches_random_access() {
+        let mut rng = Threefry2x64::new(99);
+        let words: Vec<u64> = (0..20).map(|_| rng.next()).collect();
+        for (n, w) in words.iter().enumerate() {
+            let mut r = Threefry2x64::new(99);
+            r.seek(n as u128);
+            assert_eq!(r.next::<u64>(), *w);
+            assert_eq!(r.at(n as u128 / 2)[n % 2], *w);
+        }
+    }
+
+    #[test]
+    fn dump_value_types() {
+        let mut rng = Threefry2x64::new(123456);
+
+        let a: u8 = rng.next();
+        assert_eq!(a, 107);
+
+        let b: u128 = rng.next();
+        assert_eq!(b, 49376306962444545725077130004008906345);
+
+        let c: bool = rng.next();
+        assert!(!c);
+
+        let d: f32 = rng.next();
+        assert_eq!(d, 0.1987541);
+    }
+}
@@ -0,0 +1,136 @@
+//! wyrand, the generator from Wang Yi's wyhash.
+//!
+//! A Weyl sequence `state += P0` passed through one 64x64->128 bit multiply.
+//! It is the smallest and usually the fastest generator in the crate: one
+//! `u64` of state, period 2^64, every seed (zero included) valid.
+//!
+//! [`WyRand`] follows `wyrand()` from wyhash final 4, the version also used
+//! by the `fastrand` crate, so the raw words match for the same seed.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tc_prng::wyrand::WyRand;
+//!
+//! let mut rng = WyRand::new(42);
+//! assert_eq!(rng.next::<u64>(), 14587678697106979209);
+//! ```
+
+use crate::{
+    state::{Algorithm, Codec, Persist, Reader, StateError},
+    Prng, PrngCore, Seedable,
+};
+
+/// wyhash's `wyrand`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct WyRand {
+    state: u64,
+}
+
+impl WyRand {
+    const P0: u64 = 0x2d358dccaa6c78a5;
+    const P1: u64 = 0x8bb84b93962eacc9;
+
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<V>(&mut self) -> V
+    where
+        Self: Prng<V>,
+    {
+        Prng::next_val(self)
+    }
+}
+
+impl PrngCore for WyRand {
+    type Word = u64;
+
+    fn calc(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(Self::P0);
+        let t = self.state as u128 * (self.state ^ Self::P1) as u128;
+        (t >> 64) as u64 ^ t as u64
+    }
+}
+
+impl Seedable for WyRand {
+    type Seed = [u8; 8];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(u64::from_le_bytes(seed))
+    }
+}
+
+impl Persist for WyRand {
+    type State = u64;
+
+    const ALGORITHM: Algorithm = Algorithm::WyRand;
+    const VARIANT: u8 = 64;
+    const STATE_LEN: usize = 8;
+
+    fn state(&self) -> u64 {
+        self.state
+    }
+
+    fn from_state(state: u64) -> Self {
+        Self { state }
+    }
+}
+
+impl Codec for WyRand {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.state.to_le_bytes());
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, StateError> {
+        Ok(Self::from_state(Reader(bytes).u64()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Reference values from wyhash final 4's `wyrand`.
+
+    #[test]
+    fn reference_vectors() {
+        let mut rng = WyRand::new(42);
+        assert_eq!(
+            [rng.calc(), rng.calc(), rng.calc()],
+            [
+                14587678697106979209,
+                9105053682160394182,
+                14839644324764355487
+            ]
+        );
+
+        let mut rng = WyRand::new(0);
+        assert_eq!(
+            [rng.calc(), rng.calc(), rng.calc()],
+            [11116517241604665558, 91298403691422709, 1747996488805885078]
+        );
+
+        let mut rng = WyRand::new(7);
+        let v = (0..1000).map(|_| rng.calc()).last();
+        assert_eq!(v, Some(17934215179457285669));
+    }
+
+    #[test]
+    fn dump_value_types() {
+        let mut rng = WyRand::new(123456);
+
+        let a: u8 = rng.next();
+        assert_eq!(a, 156);
+
+        let b: u128 = rng.next();
+        assert_eq!(b, 107836898533028879247384147173737023643);
+
+        let c: bool = rng.next();
+        assert!(!c);
+
+        let d: f32 = rng.next();
+        assert_eq!(d, 0.09323109);
+    }
+}
@@ -1,33 +1,63 @@
-use crate::{prng_128 as p128, prng_32 as p32, prng_64 as p64, split_mix::*};
+use std::fmt;
+
+use crate::{
+    split_mix::*,
+    state::{Algorithm, Codec, Persist, Reader, StateError},
+    Prng, PrngCore, Seedable,
+};
 
 pub trait XorshiroNew<T> {
     fn make(seed: T) -> Xorshiro<T>;
 }
 
-impl XorshiroNew<u32> for Xorshiro<u32> {
-    fn make(seed: u32) -> Xorshiro<u32> {
-        Xorshiro32 {
-            seed: SplitMix::new(seed).next(),
-        }
+/// Error from `try_from_state` for the all-zero state, which every
+/// xorshift step maps to itself.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ZeroStateError;
+
+impl fmt::Display for ZeroStateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "xorshift state must not be zero")
     }
 }
 
-impl XorshiroNew<u64> for Xorshiro<u64> {
-    fn make(seed: u64) -> Xorshiro<u64> {
-        Xorshiro64 {
-            seed: SplitMix::new(seed).next(),
+impl std::error::Error for ZeroStateError {}
+
+macro_rules! impl_new {
+    ($($t:ty),*) => {$(
+        impl XorshiroNew<$t> for Xorshiro<$t> {
+            fn make(seed: $t) -> Xorshiro<$t> {
+                Self {
+                    seed: Self::seed_from(&mut SplitMix::new(seed)),
+                }
+            }
         }
-    }
-}
 
-impl XorshiroNew<u128> for Xorshiro<u128> {
-    fn make(seed: u128) -> Xorshiro<u128> {
-        Xorshiro128 {
-            seed: SplitMix::new(seed).next(),
+        impl Xorshiro<$t> {
+            /// Starts from `state` exactly, which must not be zero.
+            pub fn try_from_state(state: $t) -> Result<Self, ZeroStateError> {
+                match state {
+                    0 => Err(ZeroStateError),
+                    seed => Ok(Self { seed }),
+                }
+            }
+
+            /// The next nonzero word of `seeder`. SplitMix's mix is a
+            /// bijection taking only 0 to 0, so at most one word is skipped.
+            pub(crate) fn seed_from(seeder: &mut SplitMix<$t>) -> $t {
+                loop {
+                    match seeder.calc() {
+                        0 => continue,
+                        seed => return seed,
+                    }
+                }
+            }
         }
-    }
+    )*};
 }
 
+impl_new!(u32, u64, u128);
+
 pub fn xorshiro<T>(seed: T) -> Xorshiro<T>
 where
     Xorshiro<T>: XorshiroNew<T>,
@@ -58,16 +88,25 @@ impl Xorshiro<u32> {
         x
     }
 
+    /// Inverse of `xorshiro`.
+    fn unxorshiro(x: u32) -> u32 {
+        let x = x.rotate_right(22);
+        let x = Self::unshl(x, 5);
+        let x = Self::unshr(x, 17);
+        Self::unshl(x, 13)
+    }
+
+    #[allow(clippy::should_implement_trait)]
     pub fn next<V>(&mut self) -> V
     where
-        Self: p32::Prng<V>,
+        Self: Prng<V>,
     {
-        p32::Prng::next_val(self)
+        Prng::next_val(self)
     }
 }
 
 impl Xorshiro<u64> {
-    fn xorshiro(mut x: u64) -> u64 {
+    pub(crate) fn xorshiro(mut x: u64) -> u64 {
         x ^= x << 13;
         x ^= x >> 7;
         x ^= x << 17;
@@ -75,11 +114,20 @@ impl Xorshiro<u64> {
         x
     }
 
+    /// Inverse of `xorshiro`.
+    fn unxorshiro(x: u64) -> u64 {
+        let x = x.rotate_right(45);
+        let x = Self::unshl(x, 17);
+        let x = Self::unshr(x, 7);
+        Self::unshl(x, 13)
+    }
+
+    #[allow(clippy::should_implement_trait)]
     pub fn next<V>(&mut self) -> V
     where
-        Self: p64::Prng<V>,
+        Self: Prng<V>,
     {
-        p64::Prng::next_val(self)
+        Prng::next_val(self)
     }
 }
 
@@ -92,39 +140,208 @@ impl Xorshiro<u128> {
         x
     }
 
+    /// Inverse of `xorshiro`.
+    fn unxorshiro(x: u128) -> u128 {
+        let x = x.rotate_right(64);
+        let x = Self::unshl(x, 7);
+        let x = Self::unshr(x, 13);
+        Self::unshl(x, 17)
+    }
+
+    #[allow(clippy::should_implement_trait)]
     pub fn next<V>(&mut self) -> V
     where
-        Self: p128::Prng<V>,
+        Self: Prng<V>,
     {
-        p128::Prng::next_val(self)
+        Prng::next_val(self)
     }
 }
 
+macro_rules! impl_jump {
+    ($($t:ty),*) => {$(
+        impl Xorshiro<$t> {
+            const BITS: usize = <$t>::BITS as usize;
+
+            /// Moves `n` steps forward in O(log n), as if `n` words had been
+            /// drawn.
+            pub fn advance(&mut self, n: u128) {
+                let step = std::array::from_fn(|j| Self::xorshiro(1 << j));
+                self.seed = Self::jump(step, n, self.seed);
+            }
+
+            /// Moves `n` steps back in O(log n), undoing `n` draws.
+            pub fn rewind(&mut self, n: u128) {
+                let step = std::array::from_fn(|j| Self::unxorshiro(1 << j));
+                self.seed = Self::jump(step, n, self.seed);
+            }
+
+            /// Applies the GF(2) matrix `m`, given by the images of the unit
+            /// vectors, `n` times to `x` by repeated squaring.
+            fn jump(mut m: [$t; Self::BITS], mut n: u128, mut x: $t) -> $t {
+                while n != 0 {
+                    if n & 1 == 1 {
+                        x = Self::apply(&m, x);
+                    }
+                    n >>= 1;
+                    if n != 0 {
+                        m = std::array::from_fn(|j| Self::apply(&m, m[j]));
+                    }
+                }
+                x
+            }
+
+            fn apply(m: &[$t; Self::BITS], x: $t) -> $t {
+                m.iter()
+                    .enumerate()
+                    .filter(|(j, _)| x >> j & 1 == 1)
+                    .fold(0, |acc, (_, col)| acc ^ col)
+            }
+
+            /// Inverse of `x ^= x << s`.
+            fn unshl(y: $t, s: u32) -> $t {
+                (0..<$t>::BITS)
+                    .step_by(s as usize)
+                    .fold(0, |x, k| x ^ (y << k))
+            }
+
+            /// Inverse of `x ^= x >> s`.
+            fn unshr(y: $t, s: u32) -> $t {
+                (0..<$t>::BITS)
+                    .step_by(s as usize)
+                    .fold(0, |x, k| x ^ (y >> k))
+            }
+        }
+    )*};
+}
+
+impl_jump!(u32, u64, u128);
+
 impl Default for Xorshiro<u32> {
     fn default() -> Self {
-        Self {
-            seed: SplitMix::new(0u32).next(),
-        }
+        Self::make(0)
     }
 }
 
 impl Default for Xorshiro<u64> {
     fn default() -> Self {
-        Self {
-            seed: SplitMix::new(0u64).next(),
-        }
+        Self::make(0)
     }
 }
 
 impl Default for Xorshiro<u128> {
     fn default() -> Self {
-        Self {
-            seed: SplitMix::new(0u128).next(),
-        }
+        Self::make(0)
+    }
+}
+
+impl Seedable for Xorshiro<u32> {
+    type Seed = [u8; 4];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::make(u32::from_le_bytes(seed))
+    }
+}
+
+impl Seedable for Xorshiro<u64> {
+    type Seed = [u8; 8];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::make(u64::from_le_bytes(seed))
+    }
+}
+
+impl Seedable for Xorshiro<u128> {
+    type Seed = [u8; 16];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::make(u128::from_le_bytes(seed))
+    }
+}
+
+impl Persist for Xorshiro<u32> {
+    type State = u32;
+
+    const ALGORITHM: Algorithm = Algorithm::Xorshiro;
+    const VARIANT: u8 = 32;
+    const STATE_LEN: usize = 4;
+
+    fn state(&self) -> u32 {
+        self.seed
+    }
+
+    /// A zero `state` is replaced by the default state.
+    fn from_state(state: u32) -> Self {
+        Self::try_from_state(state).unwrap_or_default()
+    }
+}
+
+impl Codec for Xorshiro<u32> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.seed.to_le_bytes());
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, StateError> {
+        Self::try_from_state(Reader(bytes).u32()).map_err(|_| StateError::Invalid)
     }
 }
 
-impl p32::Prng32 for Xorshiro<u32> {
+impl Persist for Xorshiro<u64> {
+    type State = u64;
+
+    const ALGORITHM: Algorithm = Algorithm::Xorshiro;
+    const VARIANT: u8 = 64;
+    const STATE_LEN: usize = 8;
+
+    fn state(&self) -> u64 {
+        self.seed
+    }
+
+    /// A zero `state` is replaced by the default state.
+    fn from_state(state: u64) -> Self {
+        Self::try_from_state(state).unwrap_or_default()
+    }
+}
+
+impl Codec for Xorshiro<u64> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.seed.to_le_bytes());
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, StateError> {
+        Self::try_from_state(Reader(bytes).u64()).map_err(|_| StateError::Invalid)
+    }
+}
+
+impl Persist for Xorshiro<u128> {
+    type State = u128;
+
+    const ALGORITHM: Algorithm = Algorithm::Xorshiro;
+    const VARIANT: u8 = 128;
+    const STATE_LEN: usize = 16;
+
+    fn state(&self) -> u128 {
+        self.seed
+    }
+
+    /// A zero `state` is replaced by the default state.
+    fn from_state(state: u128) -> Self {
+        Self::try_from_state(state).unwrap_or_default()
+    }
+}
+
+impl Codec for Xorshiro<u128> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.seed.to_le_bytes());
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, StateError> {
+        Self::try_from_state(Reader(bytes).u128()).map_err(|_| StateError::Invalid)
+    }
+}
+
+impl PrngCore for Xorshiro<u32> {
+    type Word = u32;
+
     fn calc(&mut self) -> u32 {
         let x = self.seed.wrapping_mul(5).rotate_left(7).wrapping_mul(9);
         self.seed = Self::xorshiro(self.seed);
@@ -132,7 +349,9 @@ impl p32::Prng32 for Xorshiro<u32> {
     }
 }
 
-impl p64::Prng64 for Xorshiro<u64> {
+impl PrngCore for Xorshiro<u64> {
+    type Word = u64;
+
     fn calc(&mut self) -> u64 {
         let x = self.seed.wrapping_mul(5).rotate_left(7).wrapping_mul(9);
         self.seed = Self::xorshiro(self.seed);
@@ -140,7 +359,9 @@ impl p64::Prng64 for Xorshiro<u64> {
     }
 }
 
-impl p128::Prng128 for Xorshiro<u128> {
+impl PrngCore for Xorshiro<u128> {
+    type Word = u128;
+
     fn calc(&mut self) -> u128 {
         let x = self.seed.wrapping_mul(5).rotate_left(7).wrapping_mul(9);
         self.seed = Self::xorshiro(self.seed);
@@ -157,6 +378,53 @@ mod test {
     use super::*;
 
     #[test]
+    fn steps_invert() {
+        let mut x = xorshiro(3_u64);
+        let start = x;
+        x.calc();
+        assert_eq!(Xorshiro64::unxorshiro(x.seed), start.seed);
+    }
+
+    #[test]
+    fn advance_matches_drawing() {
+        let mut a = xorshiro(11_u32);
+        let mut b = a;
+        for _ in 0..1000 {
+            a.calc();
+        }
+        b.advance(1000);
+        assert!(a == b);
+
+        let mut c = xorshiro(11_u128);
+        let mut d = c;
+        for _ in 0..37 {
+            c.calc();
+        }
+        d.advance(37);
+        assert!(c == d);
+    }
+
+    #[test]
+    fn forward_then_back_restores() {
+        let mut a = xorshiro(42_u64);
+        let start = a;
+        let first: u64 = a.next();
+        for _ in 0..99 {
+            a.calc();
+        }
+        a.rewind(100);
+        assert!(a == start);
+        assert_eq!(a.next::<u64>(), first);
+
+        let mut b = xorshiro(42_u128);
+        let start = b;
+        b.advance(u128::MAX - 5);
+        b.rewind(u128::MAX - 5);
+        assert!(b == start);
+    }
+
+    #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn dump_value_types() {
         let mut rng = xorshiro(123456_u64);
 
@@ -172,4 +440,41 @@ mod test {
         let d: f32 = rng.next();
         assert_eq!(d, 0.074927814);
     }
+
+    #[test]
+    fn seeds_never_give_zero_state() {
+        // SplitMix's first word is zero exactly for the seed `-ADD`.
+        let seed = SplitMix32::ADD.wrapping_neg();
+        assert_eq!(SplitMix32::new(seed).calc(), 0);
+        let mut rng = xorshiro(seed);
+        assert_ne!(rng.seed, 0);
+        assert_ne!(rng.calc(), rng.calc());
+
+        let seed = SplitMix64::ADD.wrapping_neg();
+        assert_eq!(SplitMix64::new(seed).calc(), 0);
+        assert_ne!(xorshiro(seed).seed, 0);
+        assert_ne!(Xorshiro64::from_seed(seed.to_le_bytes()).seed, 0);
+
+        let seed = SplitMix128::ADD.wrapping_neg();
+        assert_eq!(SplitMix128::new(seed).calc(), 0);
+        assert_ne!(xorshiro(seed).seed, 0);
+    }
+
+    #[test]
+    fn zero_state_is_rejected() {
+        assert_eq!(Xorshiro32::try_from_state(0).err(), Some(ZeroStateError));
+        assert_eq!(Xorshiro64::try_from_state(0).err(), Some(ZeroStateError));
+        assert_eq!(Xorshiro128::try_from_state(0).err(), Some(ZeroStateError));
+        assert_eq!(Xorshiro64::try_from_state(5).unwrap().state(), 5);
+
+        assert!(Xorshiro32::from_state(0) == Xorshiro32::default());
+        assert!(Xorshiro64::from_state(0) == Xorshiro64::default());
+        assert!(Xorshiro128::from_state(0) == Xorshiro128::default());
+
+        let zero = [b't', b'c', 1, 2, 64, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(
+            Xorshiro64::from_bytes(&zero).err(),
+            Some(StateError::Invalid)
+        );
+    }
 }
@@ -0,0 +1,177 @@
+//! `#[derive(Random)]`: field order, attributes and every generator width.
+
+use tc_prng::{mock::ScriptedRng, prelude::*, FromWord, Prng, Random, SampleRange};
+
+#[derive(Random, Debug, PartialEq)]
+struct Point {
+    x: u8,
+    y: bool,
+    z: f32,
+}
+
+#[derive(Random, Debug, PartialEq)]
+struct Stats(
+    #[random(range = 1..=6)] u32,
+    #[random(range = -1.0..1.0)] f64,
+);
+
+#[derive(Random, Debug, PartialEq)]
+struct Unit;
+
+fn always_seven<G: Prng<u64> + ?Sized>(_: &mut G) -> u16 {
+    7
+}
+
+fn d20<G: Prng<u64> + ?Sized>(rng: &mut G) -> u8 {
+    (1..=20).sample(rng)
+}
+
+#[derive(Random, Debug, PartialEq)]
+struct Custom {
+    #[random(with = always_seven)]
+    seven: u16,
+    #[random(with = d20)]
+    roll: u8,
+}
+
+#[derive(Random, Debug, PartialEq)]
+enum Loot {
+    #[random(weight = 6)]
+    Nothing,
+    #[random(weight = 3)]
+    Gold(#[random(range = 1..50)] u16),
+    Sword {
+        sharpness: f32,
+    },
+    #[random(weight = 0)]
+    Cursed,
+}
+
+#[derive(Random, Debug, PartialEq)]
+struct Pair<T> {
+    a: T,
+    b: Option<T>,
+}
+
+mod reexport {
+    pub use tc_prng as renamed;
+}
+
+#[derive(Random, Debug, PartialEq)]
+#[random(crate = "crate::reexport::renamed")]
+struct Renamed {
+    #[random(range = 0..3)]
+    v: u8,
+}
+
+#[test]
+fn fields_in_declaration_order() {
+    let words = vec![5_u64 << 48, 1 << 32, 0, 9 << 48, 0];
+    let expected = Point {
+        x: 5,
+        y: true,
+        z: 0.0,
+    };
+    assert_eq!(ScriptedRng::new(words.clone()).next::<Point>(), expected);
+
+    // The same draws, one field at a time.
+    let mut a = ScriptedRng::new(words.clone());
+    let manual = Point {
+        x: a.next(),
+        y: a.next(),
+        z: a.next(),
+    };
+    assert_eq!(manual, expected);
+
+    let mut rng = ScriptedRng::new(words);
+    rng.next::<Point>();
+    assert_eq!(rng.next::<Pair<u8>>(), Pair { a: 9, b: None });
+    assert_eq!(rng.remaining(), 0);
+}
+
+#[test]
+fn ranges_and_samplers() {
+    let mut rng = xorshiro(11_u64);
+    for _ in 0..1000 {
+        let Stats(d6, unit) = rng.next();
+        assert!((1..=6).contains(&d6));
+        assert!((-1.0..1.0).contains(&unit));
+
+        let custom: Custom = rng.next();
+        assert_eq!(custom.seven, 7);
+        assert!((1..=20).contains(&custom.roll));
+
+        assert!(rng.next::<Renamed>().v < 3);
+        assert_eq!(rng.next::<Unit>(), Unit);
+    }
+
+    // Same values as drawing the ranges by hand.
+    let mut a = xorshiro(3_u64);
+    let mut b = a;
+    assert_eq!(
+        a.next::<Stats>(),
+        Stats(b.next_range(1..=6), b.next_range(-1.0..1.0))
+    );
+}
+
+#[test]
+fn enum_weights() {
+    let mut rng = split_mix(99_u64);
+    let mut counts = [0; 3];
+    for _ in 0..10_000 {
+        let loot = rng.next::<Loot>();
+        assert_ne!(loot, Loot::Cursed);
+        match loot {
+            Loot::Nothing => counts[0] += 1,
+            Loot::Gold(n) => {
+                assert!((1..50).contains(&n));
+                counts[1] += 1;
+            }
+            Loot::Sword { .. } => counts[2] += 1,
+            Loot::Cursed => unreachable!(),
+        }
+    }
+    // Expected 6000, 3000 and 1000.
+    assert!((5700..6300).contains(&counts[0]), "{counts:?}");
+    assert!((2700..3300).contains(&counts[1]), "{counts:?}");
+    assert!((850..1150).contains(&counts[2]), "{counts:?}");
+
+    // One draw of 0..10 picks the variant: 9 is the last slot, Sword.
+    let mut rng = ScriptedRng::new(vec![u64::MAX, 0]);
+    assert_eq!(rng.next::<Loot>(), Loot::Sword { sharpness: 0.0 });
+}
+
+#[test]
+fn every_width() {
+    fn draw<G: PrngCore>(rng: &mut G) -> (Point, Stats, Loot, Pair<i16>)
+    where
+        (Point, Stats, Loot, Pair<i16>): FromWord<G::Word>,
+    {
+        rng.next()
+    }
+    draw(&mut xorshiro(1_u32));
+    draw(&mut xorshiro(1_u64));
+    draw(&mut xorshiro(1_u128));
+    draw(&mut tc_prng::mt::Mt19937::new(1));
+}
+
+#[test]
+fn dump_value_types() {
+    let mut rng = split_mix(123456_u64);
+
+    let a = rng.next::<Point>();
+    assert_eq!(
+        a,
+        Point {
+            x: 230,
+            y: false,
+            z: 0.29477158
+        }
+    );
+
+    let b = rng.next::<Loot>();
+    assert_eq!(b, Loot::Gold(21));
+
+    let c = rng.next::<Stats>();
+    assert_eq!(c, Stats(5, -0.24686490729752175));
+}
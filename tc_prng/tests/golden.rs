@@ -0,0 +1,366 @@
+//! Output stability: every generator, width, output type and distribution
+//! against the vectors recorded in `tests/golden/v<major version>`.
+//!
+//! Any difference fails. Only regenerate the files with
+//!
+//! ```text
+//! TC_PRNG_BLESS=1 cargo test -p tc_prng --test golden
+//! ```
+//!
+//! when adding coverage, or for a new major version in its own directory.
+
+use std::{
+    fmt::Debug,
+    fmt::Write as _,
+    fs,
+    num::{NonZeroU8, Wrapping},
+    path::PathBuf,
+};
+
+use tc_prng::{
+    bits::BitSource,
+    chacha::{ChaCha12, ChaCha20, ChaCha8},
+    jsf::{Jsf32, Jsf64},
+    lanes::{SplitMix64x4, SplitMix64x8, Xorshiro64x4, Xorshiro64x8},
+    lcg::{AnsiCRand, Drand48, GlibcRand, MinStdRand, MinStdRand0},
+    mt::{Mt19937, Mt19937_64},
+    noise::{Fractal, NoiseFn, OpenSimplex2, Perlin, Simplex, Worley},
+    normal::Normal,
+    par::par_fill,
+    philox::Philox4x32,
+    positional::{hash_at, rng_at},
+    prelude::*,
+    romu::{RomuDuo, RomuDuoJr, RomuQuad, RomuQuad32, RomuTrio, RomuTrio32},
+    sfc::{Sfc32, Sfc64},
+    threefry::Threefry2x64,
+    wyrand::WyRand,
+    SeedSequence,
+};
+
+/// Values recorded per section.
+const COUNT: usize = 16;
+const SEED: u64 = 0x7c_5eed;
+
+const BLESS_VAR: &str = "TC_PRNG_BLESS";
+/// Directory of the vectors for the current major version.
+const VERSION_DIR: &str = "v1";
+
+/// Every value type a generator of any width can produce.
+trait Golden:
+    Clone
+    + Prng<u8>
+    + Prng<u16>
+    + Prng<u32>
+    + Prng<u64>
+    + Prng<u128>
+    + Prng<i8>
+    + Prng<i16>
+    + Prng<i32>
+    + Prng<i64>
+    + Prng<i128>
+    + Prng<f32>
+    + Prng<f64>
+    + Prng<bool>
+    + PrngCore
+{
+}
+
+impl<G> Golden for G where
+    G: Clone
+        + Prng<u8>
+        + Prng<u16>
+        + Prng<u32>
+        + Prng<u64>
+        + Prng<u128>
+        + Prng<i8>
+        + Prng<i16>
+        + Prng<i32>
+        + Prng<i64>
+        + Prng<i128>
+        + Prng<f32>
+        + Prng<f64>
+        + Prng<bool>
+        + PrngCore
+{
+}
+
+/// Builds the text of one golden file, one `name = values` line per
+/// section.
+#[derive(Default)]
+struct Sections(String);
+
+impl Sections {
+    fn line<T: Debug>(&mut self, name: &str, values: impl IntoIterator<Item = T>) {
+        let values: Vec<_> = values.into_iter().map(|v| format!("{v:?}")).collect();
+        writeln!(self.0, "{name} = {}", values.join(" ")).unwrap();
+    }
+
+    /// `COUNT` values drawn by `f` from a fresh copy of `rng`, so a change
+    /// to one conversion only shows up in its own section.
+    fn draw<G: Clone, T: Debug>(&mut self, name: &str, rng: &G, mut f: impl FnMut(&mut G) -> T) {
+        let mut g = rng.clone();
+        self.line(name, (0..COUNT).map(|_| f(&mut g)));
+    }
+}
+
+fn generator<G: Golden>(rng: G) -> String {
+    let mut s = Sections::default();
+    s.draw("u8", &rng, |g| Prng::<u8>::next_val(g));
+    s.draw("u16", &rng, |g| Prng::<u16>::next_val(g));
+    s.draw("u32", &rng, |g| Prng::<u32>::next_val(g));
+    s.draw("u64", &rng, |g| Prng::<u64>::next_val(g));
+    s.draw("u128", &rng, |g| Prng::<u128>::next_val(g));
+    s.draw("i8", &rng, |g| Prng::<i8>::next_val(g));
+    s.draw("i16", &rng, |g| Prng::<i16>::next_val(g));
+    s.draw("i32", &rng, |g| Prng::<i32>::next_val(g));
+    s.draw("i64", &rng, |g| Prng::<i64>::next_val(g));
+    s.draw("i128", &rng, |g| Prng::<i128>::next_val(g));
+    s.draw("f32", &rng, |g| Prng::<f32>::next_val(g));
+    s.draw("f64", &rng, |g| Prng::<f64>::next_val(g));
+    s.draw("bool", &rng, |g| Prng::<bool>::next_val(g));
+
+    s.draw("range u8 0..10", &rng, |g| g.next_range(0..10_u8));
+    s.draw("range u32 1..=6", &rng, |g| g.next_range(1..=6_u32));
+    s.draw("range u64 0..1e12", &rng, |g| {
+        g.next_range(0..1_000_000_000_000_u64)
+    });
+    s.draw("range u64 full", &rng, |g| g.next_range(0..=u64::MAX));
+    // `usize` and `isize` draw the same words on every target, but 32-bit
+    // targets keep only the low half, so they are recorded as `u64` / `i64`.
+    s.draw("range u64 0..1000", &rng, |g| g.next_range(0..1000_u64));
+    s.draw("range i16 -300..300", &rng, |g| g.next_range(-300..300_i16));
+    s.draw("range i64 -5..=5", &rng, |g| g.next_range(-5..=5_i64));
+    s.draw("range i64 -1..1", &rng, |g| g.next_range(-1..1_i64));
+    s.draw("range f32 -1..=1", &rng, |g| g.next_range(-1.0..=1.0_f32));
+    s.draw("range f64 0..1", &rng, |g| g.next_range(0.0..1.0_f64));
+    s.draw("range f64 10..20", &rng, |g| g.next_range(10.0..20.0_f64));
+    s.0
+}
+
+fn generators() -> Vec<(&'static str, String)> {
+    vec![
+        ("split_mix32", generator(split_mix(SEED as u32))),
+        ("split_mix64", generator(split_mix(SEED))),
+        ("split_mix128", generator(split_mix(SEED as u128))),
+        ("xorshiro32", generator(xorshiro(SEED as u32))),
+        ("xorshiro64", generator(xorshiro(SEED))),
+        ("xorshiro128", generator(xorshiro(SEED as u128))),
+        ("chacha8", generator(ChaCha8::with_stream([7; 32], 3))),
+        ("chacha12", generator(ChaCha12::with_stream([7; 32], 3))),
+        ("chacha20", generator(ChaCha20::with_stream([7; 32], 3))),
+        ("philox4x32", generator(Philox4x32::new(SEED))),
+        ("threefry2x64", generator(Threefry2x64::new(SEED))),
+        ("mt19937", generator(Mt19937::new(SEED as u32))),
+        ("mt19937_64", generator(Mt19937_64::new(SEED))),
+        ("minstd_rand0", generator(MinStdRand0::new(SEED as u32))),
+        ("minstd_rand", generator(MinStdRand::new(SEED as u32))),
+        ("drand48", generator(Drand48::new(SEED as i64))),
+        ("glibc_rand", generator(GlibcRand::new(SEED as u32))),
+        ("ansi_c_rand", generator(AnsiCRand::new(SEED as u32))),
+        ("wyrand", generator(WyRand::new(SEED))),
+        ("sfc32", generator(Sfc32::new(SEED))),
+        ("sfc64", generator(Sfc64::new(SEED))),
+        ("jsf32", generator(Jsf32::new(SEED as u32))),
+        ("jsf64", generator(Jsf64::new(SEED))),
+        ("romu_quad", generator(RomuQuad::new(SEED))),
+        ("romu_trio", generator(RomuTrio::new(SEED))),
+        ("romu_duo", generator(RomuDuo::new(SEED))),
+        ("romu_duo_jr", generator(RomuDuoJr::new(SEED))),
+        ("romu_quad32", generator(RomuQuad32::new(SEED))),
+        ("romu_trio32", generator(RomuTrio32::new(SEED))),
+    ]
+}
+
+/// Outputs built on top of the generators: seeding helpers, legacy entry
+/// points, batch APIs and noise.
+fn derived() -> String {
+    let mut s = Sections::default();
+
+    let seq = SeedSequence::new(SEED as u128);
+    s.line("seed_seq u32", seq.generate_u32(COUNT));
+    s.line("seed_seq child u64", seq.child(3).generate_u64(COUNT));
+    s.draw(
+        "from_seed_str xorshiro64",
+        &Xorshiro64::from_seed_str("level-3/boss"),
+        |g| g.next::<u64>(),
+    );
+
+    s.line("hash_at", (0..COUNT as i64).map(|x| hash_at(SEED, (x, -x))));
+    s.line(
+        "rng_at f32",
+        (0..COUNT as i64).map(|x| rng_at::<f32, _>(SEED, [x, 2, 3])),
+    );
+
+    let mut lanes = SplitMix64x4::new(SEED);
+    s.line(
+        "split_mix64x4",
+        (0..COUNT / 4).flat_map(|_| lanes.next_block()),
+    );
+    let mut lanes = SplitMix64x8::new(SEED);
+    s.line(
+        "split_mix64x8",
+        (0..COUNT / 8).flat_map(|_| lanes.next_block()),
+    );
+    let mut lanes = Xorshiro64x4::new(SEED);
+    s.line(
+        "xorshiro64x4",
+        (0..COUNT / 4).flat_map(|_| lanes.next_block()),
+    );
+    let mut lanes = Xorshiro64x8::new(SEED);
+    s.line(
+        "xorshiro64x8",
+        (0..COUNT / 8).flat_map(|_| lanes.next_block()),
+    );
+    let mut floats = [0.0_f32; COUNT];
+    Xorshiro64x8::new(SEED).fill_f32(&mut floats);
+    s.line("xorshiro64x8 f32", floats);
+
+    // Two chunks, so the chunk seeding is covered too.
+    let mut big = vec![0_u32; tc_prng::par::CHUNK_LEN + COUNT];
+    par_fill(&mut big, SEED, 2);
+    s.line("par_fill u32 head", big[..COUNT].iter());
+    s.line(
+        "par_fill u32 second chunk",
+        big[tc_prng::par::CHUNK_LEN..].iter(),
+    );
+
+    let mut bits = BitSource::new(xorshiro(SEED));
+    s.line("bits next_bits 5", (0..COUNT).map(|_| bits.next_bits(5)));
+    s.line("bits next_below 7", (0..COUNT).map(|_| bits.next_below(7)));
+
+    let mut rng = Drand48::new(SEED as i64);
+    s.line("drand48", (0..COUNT).map(|_| rng.drand48()));
+    s.line("lrand48", (0..COUNT).map(|_| rng.lrand48()));
+    s.line("mrand48", (0..COUNT).map(|_| rng.mrand48()));
+    let mut rng = GlibcRand::new(SEED as u32);
+    s.line("glibc rand", (0..COUNT).map(|_| rng.rand()));
+
+    let mut bytes = [0_u8; COUNT];
+    ChaCha20::new([7; 32]).fill_bytes(&mut bytes);
+    s.line("chacha20 fill_bytes", bytes);
+    s.line("philox at", Philox4x32::new(SEED).at(1 << 70));
+    s.line("threefry at", Threefry2x64::new(SEED).at(1 << 70));
+
+    // Compound types, for each word width.
+    let r32 = xorshiro(SEED as u32);
+    let r64 = xorshiro(SEED);
+    let r128 = xorshiro(SEED as u128);
+    s.draw("compound 32", &r32, |g| {
+        g.next::<([u8; 3], (bool, i16), Option<f32>)>()
+    });
+    s.draw("compound 64", &r64, |g| {
+        g.next::<([u8; 3], (bool, i16), Option<f32>)>()
+    });
+    s.draw("compound 128", &r128, |g| {
+        g.next::<([u8; 3], (bool, i16), Option<f32>)>()
+    });
+    s.draw("char 32", &r32, |g| g.next::<char>());
+    s.draw("char 64", &r64, |g| g.next::<char>());
+    s.draw("char 128", &r128, |g| g.next::<char>());
+    s.draw("u64 i64 nonzero 32", &r32, |g| {
+        g.next::<(u64, i64, NonZeroU8, Wrapping<u16>)>()
+    });
+    s.draw("u64 i64 nonzero 64", &r64, |g| {
+        g.next::<(u64, i64, NonZeroU8, Wrapping<u16>)>()
+    });
+    s.draw("u64 i64 nonzero 128", &r128, |g| {
+        g.next::<(u64, i64, NonZeroU8, Wrapping<u16>)>()
+    });
+
+    // `ln` may differ in the last bit between platforms, so normal values
+    // are recorded to 12 decimals.
+    let normal = Normal::new(5.0, 2.0);
+    s.draw("normal", &r64, |g| (normal.sample(g) * 1e12).round() / 1e12);
+
+    let points = |n: usize| (0..n).map(|i| i as f64 * 0.37 - 2.1);
+    let perlin = Perlin::new(SEED);
+    s.line(
+        "perlin 2d",
+        points(COUNT).map(|x| perlin.sample([x, x * 0.5])),
+    );
+    s.line(
+        "perlin 3d",
+        points(COUNT).map(|x| perlin.sample([x, 1.5, -x])),
+    );
+    let simplex = Simplex::new(SEED);
+    s.line(
+        "simplex 2d",
+        points(COUNT).map(|x| simplex.sample([x, x * 0.5])),
+    );
+    s.line(
+        "simplex 4d",
+        points(COUNT).map(|x| simplex.sample([x, 1.5, -x, 0.25])),
+    );
+    let open_simplex = OpenSimplex2::new(SEED);
+    s.line(
+        "open simplex2 3d",
+        points(COUNT).map(|x| open_simplex.sample([x, 1.5, -x])),
+    );
+    let worley = Worley::new(SEED);
+    s.line(
+        "worley 2d",
+        points(COUNT).map(|x| worley.sample([x, x * 0.5])),
+    );
+    let fbm = Fractal::fbm(Simplex::new(SEED)).octaves(4);
+    s.line(
+        "fbm simplex 2d",
+        points(COUNT).map(|x| fbm.sample([x, x * 0.5])),
+    );
+
+    s.0
+}
+
+fn golden_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(VERSION_DIR)
+}
+
+/// Compares `actual` with the golden file `name`, or rewrites the file when
+/// blessing. Returns a description of the first difference.
+fn check(name: &str, actual: &str) -> Option<String> {
+    let path = golden_dir().join(format!("{name}.txt"));
+    if std::env::var_os(BLESS_VAR).is_some() {
+        fs::create_dir_all(golden_dir()).unwrap();
+        fs::write(&path, actual).unwrap();
+        return None;
+    }
+
+    let Ok(expected) = fs::read_to_string(&path) else {
+        return Some(format!("{name}: missing {}", path.display()));
+    };
+    let mut expected_lines = expected.lines();
+    for line in actual.lines() {
+        match expected_lines.next() {
+            Some(e) if e == line => {}
+            Some(e) => return Some(format!("{name}:\n  expected {e}\n  actual   {line}")),
+            None => return Some(format!("{name}: new section {line}")),
+        }
+    }
+    expected_lines
+        .next()
+        .map(|e| format!("{name}: missing section {e}"))
+}
+
+fn assert_golden(files: Vec<(&str, String)>) {
+    let failures: Vec<_> = files
+        .iter()
+        .filter_map(|(name, actual)| check(name, actual))
+        .collect();
+    assert!(
+        failures.is_empty(),
+        "output changed, which breaks the output stability contract:\n{}\n\n\
+         If this is intended, see \"Output stability\" in the README.",
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn generator_outputs_are_stable() {
+    assert_golden(generators());
+}
+
+#[test]
+fn derived_outputs_are_stable() {
+    assert_golden(vec![("derived", derived())]);
+}
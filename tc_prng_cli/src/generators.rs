@@ -0,0 +1,269 @@
+//! The generators the command line can name, behind one object safe trait.
+
+use std::fmt::{Debug, Write as _};
+
+use tc_prng::{
+    chacha::{ChaCha12, ChaCha20, ChaCha8},
+    jsf::{Jsf32, Jsf64},
+    lcg::{AnsiCRand, Drand48, GlibcRand, MinStdRand, MinStdRand0},
+    mt::{Mt19937, Mt19937_64},
+    normal::Normal,
+    philox::Philox4x32,
+    replay::Word,
+    romu::{RomuDuo, RomuDuoJr, RomuQuad, RomuQuad32, RomuTrio, RomuTrio32},
+    sfc::{Sfc32, Sfc64},
+    split_mix,
+    threefry::Threefry2x64,
+    wyrand::WyRand,
+    xorshiro, Persist, Prng, PrngCore,
+};
+
+/// Names accepted by `--type`, in the order `list` prints them.
+pub const VALUE_TYPES: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "i8", "i16", "i32", "i64", "i128", "f32", "f64", "bool",
+    "normal",
+];
+
+/// Builds a generator from the seed.
+pub type Build = fn(u64) -> Box<dyn Generator>;
+
+/// Names accepted for the generator, with the constructor the seed is
+/// passed to and the function building it. Seeds wider than the constructor
+/// takes are truncated.
+pub const GENERATORS: &[(&str, &str, Build)] = &[
+    ("split_mix32", "split_mix(seed as u32)", |seed| {
+        Box::new(split_mix(seed as u32))
+    }),
+    ("split_mix64", "split_mix(seed)", |seed| {
+        Box::new(split_mix(seed))
+    }),
+    ("split_mix128", "split_mix(seed as u128)", |seed| {
+        Box::new(split_mix(seed as u128))
+    }),
+    ("xorshiro32", "xorshiro(seed as u32)", |seed| {
+        Box::new(xorshiro(seed as u32))
+    }),
+    ("xorshiro64", "xorshiro(seed)", |seed| {
+        Box::new(xorshiro(seed))
+    }),
+    ("xorshiro128", "xorshiro(seed as u128)", |seed| {
+        Box::new(xorshiro(seed as u128))
+    }),
+    (
+        "chacha8",
+        "ChaCha8::new(key), key = seed little-endian, zero padded",
+        |seed| Box::new(ChaCha8::new(key(seed))),
+    ),
+    (
+        "chacha12",
+        "ChaCha12::new(key), key = seed little-endian, zero padded",
+        |seed| Box::new(ChaCha12::new(key(seed))),
+    ),
+    (
+        "chacha20",
+        "ChaCha20::new(key), key = seed little-endian, zero padded",
+        |seed| Box::new(ChaCha20::new(key(seed))),
+    ),
+    ("philox4x32", "Philox4x32::new(seed)", |seed| {
+        Box::new(Philox4x32::new(seed))
+    }),
+    ("threefry2x64", "Threefry2x64::new(seed)", |seed| {
+        Box::new(Threefry2x64::new(seed))
+    }),
+    ("mt19937", "Mt19937::new(seed as u32)", |seed| {
+        Box::new(Mt19937::new(seed as u32))
+    }),
+    ("mt19937_64", "Mt19937_64::new(seed)", |seed| {
+        Box::new(Mt19937_64::new(seed))
+    }),
+    ("minstd_rand0", "MinStdRand0::new(seed as u32)", |seed| {
+        Box::new(MinStdRand0::new(seed as u32))
+    }),
+    ("minstd_rand", "MinStdRand::new(seed as u32)", |seed| {
+        Box::new(MinStdRand::new(seed as u32))
+    }),
+    ("drand48", "Drand48::new(seed as i64)", |seed| {
+        Box::new(Drand48::new(seed as i64))
+    }),
+    ("glibc_rand", "GlibcRand::new(seed as u32)", |seed| {
+        Box::new(GlibcRand::new(seed as u32))
+    }),
+    ("ansi_c_rand", "AnsiCRand::new(seed as u32)", |seed| {
+        Box::new(AnsiCRand::new(seed as u32))
+    }),
+    ("wyrand", "WyRand::new(seed)", |seed| {
+        Box::new(WyRand::new(seed))
+    }),
+    ("sfc32", "Sfc32::new(seed)", |seed| {
+        Box::new(Sfc32::new(seed))
+    }),
+    ("sfc64", "Sfc64::new(seed)", |seed| {
+        Box::new(Sfc64::new(seed))
+    }),
+    ("jsf32", "Jsf32::new(seed as u32)", |seed| {
+        Box::new(Jsf32::new(seed as u32))
+    }),
+    ("jsf64", "Jsf64::new(seed)", |seed| {
+        Box::new(Jsf64::new(seed))
+    }),
+    ("romu_quad", "RomuQuad::new(seed)", |seed| {
+        Box::new(RomuQuad::new(seed))
+    }),
+    ("romu_trio", "RomuTrio::new(seed)", |seed| {
+        Box::new(RomuTrio::new(seed))
+    }),
+    ("romu_duo", "RomuDuo::new(seed)", |seed| {
+        Box::new(RomuDuo::new(seed))
+    }),
+    ("romu_duo_jr", "RomuDuoJr::new(seed)", |seed| {
+        Box::new(RomuDuoJr::new(seed))
+    }),
+    ("romu_quad32", "RomuQuad32::new(seed)", |seed| {
+        Box::new(RomuQuad32::new(seed))
+    }),
+    ("romu_trio32", "RomuTrio32::new(seed)", |seed| {
+        Box::new(RomuTrio32::new(seed))
+    }),
+];
+
+/// A generator of any algorithm and width.
+pub trait Generator {
+    /// Size of one raw output word.
+    fn word_bytes(&self) -> usize;
+
+    /// Calculates one raw word and appends it in little-endian order.
+    fn write_word(&mut self, out: &mut Vec<u8>);
+
+    /// Draws one value of a type from [`VALUE_TYPES`] and formats it, or
+    /// returns `None` for an unknown type.
+    fn value(&mut self, ty: &str) -> Option<String>;
+
+    /// The `Debug` form of the [`Persist::State`].
+    fn state(&self) -> String;
+
+    /// [`Persist::to_bytes`] as lowercase hex.
+    fn state_hex(&self) -> String;
+}
+
+impl<G> Generator for G
+where
+    G: PrngCore
+        + Persist
+        + Prng<u8>
+        + Prng<u16>
+        + Prng<u32>
+        + Prng<u64>
+        + Prng<u128>
+        + Prng<i8>
+        + Prng<i16>
+        + Prng<i32>
+        + Prng<i64>
+        + Prng<i128>
+        + Prng<f32>
+        + Prng<f64>
+        + Prng<bool>,
+    G::Word: Word,
+    G::State: Debug,
+{
+    fn word_bytes(&self) -> usize {
+        G::Word::BYTES
+    }
+
+    fn write_word(&mut self, out: &mut Vec<u8>) {
+        self.calc().write_le(out);
+    }
+
+    fn value(&mut self, ty: &str) -> Option<String> {
+        fn show<T: Debug>(value: T) -> Option<String> {
+            Some(format!("{value:?}"))
+        }
+        match ty {
+            "u8" => show(Prng::<u8>::next_val(self)),
+            "u16" => show(Prng::<u16>::next_val(self)),
+            "u32" => show(Prng::<u32>::next_val(self)),
+            "u64" => show(Prng::<u64>::next_val(self)),
+            "u128" => show(Prng::<u128>::next_val(self)),
+            "i8" => show(Prng::<i8>::next_val(self)),
+            "i16" => show(Prng::<i16>::next_val(self)),
+            "i32" => show(Prng::<i32>::next_val(self)),
+            "i64" => show(Prng::<i64>::next_val(self)),
+            "i128" => show(Prng::<i128>::next_val(self)),
+            "f32" => show(Prng::<f32>::next_val(self)),
+            "f64" => show(Prng::<f64>::next_val(self)),
+            "bool" => show(Prng::<bool>::next_val(self)),
+            "normal" => show(Normal::STANDARD.sample(self)),
+            _ => None,
+        }
+    }
+
+    fn state(&self) -> String {
+        format!("{:?}", Persist::state(self))
+    }
+
+    fn state_hex(&self) -> String {
+        self.to_bytes().iter().fold(String::new(), |mut s, b| {
+            write!(s, "{b:02x}").unwrap();
+            s
+        })
+    }
+}
+
+/// Builds the generator called `name`, or returns `None` for an unknown name.
+pub fn build(name: &str, seed: u64) -> Option<Box<dyn Generator>> {
+    GENERATORS
+        .iter()
+        .find(|(n, ..)| *n == name)
+        .map(|(_, _, build)| build(seed))
+}
+
+/// The ChaCha key for `seed`: its little-endian bytes, zero padded.
+fn key(seed: u64) -> [u8; 32] {
+    let mut key = [0; 32];
+    key[..8].copy_from_slice(&seed.to_le_bytes());
+    key
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn every_listed_generator_builds() {
+        for (name, ..) in GENERATORS {
+            let mut rng = build(name, 42).unwrap_or_else(|| panic!("{name}"));
+            for ty in VALUE_TYPES {
+                assert!(rng.value(ty).is_some(), "{name} {ty}");
+            }
+        }
+        assert!(build("nope", 42).is_none());
+        assert!(build("xorshiro64", 42).unwrap().value("u7").is_none());
+    }
+
+    #[test]
+    fn same_output_as_library() {
+        let mut lib = xorshiro(42_u64);
+        let mut cli = build("xorshiro64", 42).unwrap();
+        assert_eq!(cli.value("u8").unwrap(), lib.next::<u8>().to_string());
+        assert_eq!(
+            cli.value("f64").unwrap(),
+            format!("{:?}", lib.next::<f64>())
+        );
+        assert_eq!(
+            cli.value("normal").unwrap(),
+            format!("{:?}", Normal::STANDARD.sample(&mut lib))
+        );
+
+        let mut words = Vec::new();
+        cli.write_word(&mut words);
+        assert_eq!(words, lib.calc().to_le_bytes());
+        assert_eq!(cli.word_bytes(), 8);
+        assert_eq!(cli.state(), format!("{:?}", Persist::state(&lib)));
+    }
+
+    #[test]
+    fn state_hex_has_header() {
+        let rng = build("split_mix32", 1).unwrap();
+        // Magic "tc", version 1, SplitMix, 32 bits, then the state.
+        assert_eq!(rng.state_hex(), "746301012001000000");
+    }
+}
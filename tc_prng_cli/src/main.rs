@@ -0,0 +1,327 @@
+//! `tc-prng`: streams and inspects the output of the `tc_prng` generators.
+//!
+//! Run `tc-prng help` for the commands.
+
+mod generators;
+
+use std::{
+    fmt,
+    io::{self, Write},
+    process::ExitCode,
+};
+
+use generators::{Generator, GENERATORS, VALUE_TYPES};
+
+const USAGE: &str = "\
+usage: tc-prng <command> [options]
+
+commands:
+  stream <generator>   write raw little-endian words to stdout
+  values <generator>   print values as text
+  state <generator>    print the internal state
+  list                 list generators and value types
+  help                 show this message
+
+options:
+  --seed <n>           seed, decimal or 0x hex (default 0)
+  --count <n>          words for stream (default: endless), values for
+                       values (default 10)
+  --type <t>           value type for values (default u64)
+  --format <f>         text, csv or json for values (default text)
+  --steps <k>          raw words to draw before state (default 0)
+";
+
+#[derive(Debug, PartialEq)]
+enum Error {
+    Usage(String),
+    Io(io::ErrorKind),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Usage(message) => write!(f, "{message}"),
+            Error::Io(kind) => write!(f, "write failed: {kind}"),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e.kind())
+    }
+}
+
+fn usage(message: impl Into<String>) -> Error {
+    Error::Usage(message.into())
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Format {
+    Text,
+    Csv,
+    Json,
+}
+
+/// Parsed command line options.
+#[derive(Debug, PartialEq)]
+struct Options {
+    seed: u64,
+    count: Option<u64>,
+    ty: String,
+    format: Format,
+    steps: u64,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            count: None,
+            ty: "u64".into(),
+            format: Format::Text,
+            steps: 0,
+        }
+    }
+}
+
+fn parse_number(flag: &str, value: &str) -> Result<u64, Error> {
+    let parsed = match value.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => value.parse(),
+    };
+    parsed.map_err(|_| usage(format!("{flag}: invalid number '{value}'")))
+}
+
+fn parse_options(args: &[String]) -> Result<Options, Error> {
+    let mut options = Options::default();
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| usage(format!("{flag}: missing value")))?;
+        match flag.as_str() {
+            "--seed" => options.seed = parse_number(flag, value)?,
+            "--count" => options.count = Some(parse_number(flag, value)?),
+            "--steps" => options.steps = parse_number(flag, value)?,
+            "--type" => {
+                if !VALUE_TYPES.contains(&value.as_str()) {
+                    return Err(usage(format!("unknown type '{value}'")));
+                }
+                options.ty = value.clone();
+            }
+            "--format" => {
+                options.format = match value.as_str() {
+                    "text" => Format::Text,
+                    "csv" => Format::Csv,
+                    "json" => Format::Json,
+                    _ => return Err(usage(format!("unknown format '{value}'"))),
+                }
+            }
+            _ => return Err(usage(format!("unknown option '{flag}'"))),
+        }
+    }
+    Ok(options)
+}
+
+fn generator(name: Option<&String>, seed: u64) -> Result<Box<dyn Generator>, Error> {
+    let name = name.ok_or_else(|| usage("missing generator, see 'tc-prng list'"))?;
+    generators::build(name, seed)
+        .ok_or_else(|| usage(format!("unknown generator '{name}', see 'tc-prng list'")))
+}
+
+/// Raw words, written in blocks so the output can be piped into test suites
+/// that read bytes.
+fn stream(rng: &mut dyn Generator, count: Option<u64>, out: &mut dyn Write) -> Result<(), Error> {
+    const BLOCK: u64 = 4096;
+    let mut buf = Vec::with_capacity(BLOCK as usize * rng.word_bytes());
+    let mut left = count;
+    loop {
+        let n = left.map_or(BLOCK, |left| left.min(BLOCK));
+        if n == 0 {
+            return Ok(());
+        }
+        buf.clear();
+        for _ in 0..n {
+            rng.write_word(&mut buf);
+        }
+        out.write_all(&buf)?;
+        left = left.map(|left| left - n);
+    }
+}
+
+fn values(rng: &mut dyn Generator, options: &Options, out: &mut dyn Write) -> Result<(), Error> {
+    let ty = &options.ty;
+    if options.format == Format::Csv {
+        writeln!(out, "index,value")?;
+    }
+    for i in 0..options.count.unwrap_or(10) {
+        let value = rng.value(ty).expect("type checked while parsing");
+        match options.format {
+            Format::Text => writeln!(out, "{value}")?,
+            Format::Csv => writeln!(out, "{i},{value}")?,
+            // Many JSON readers parse numbers as f64, which cannot hold every
+            // 64 or 128 bit integer, so those are written as strings.
+            Format::Json if matches!(ty.as_str(), "u64" | "u128" | "i64" | "i128") => {
+                writeln!(out, r#"{{"index":{i},"value":"{value}"}}"#)?
+            }
+            Format::Json => writeln!(out, r#"{{"index":{i},"value":{value}}}"#)?,
+        }
+    }
+    Ok(())
+}
+
+fn state(rng: &mut dyn Generator, steps: u64, out: &mut dyn Write) -> Result<(), Error> {
+    let mut discard = Vec::new();
+    for _ in 0..steps {
+        discard.clear();
+        rng.write_word(&mut discard);
+    }
+    writeln!(out, "steps: {steps}")?;
+    writeln!(out, "state: {}", rng.state())?;
+    writeln!(out, "bytes: {}", rng.state_hex())?;
+    Ok(())
+}
+
+fn list(out: &mut dyn Write) -> Result<(), Error> {
+    writeln!(out, "generators:")?;
+    for (name, constructor, _) in GENERATORS {
+        writeln!(out, "  {name:<14} {constructor}")?;
+    }
+    writeln!(out, "types: {}", VALUE_TYPES.join(" "))?;
+    Ok(())
+}
+
+fn run(args: &[String], out: &mut dyn Write) -> Result<(), Error> {
+    let Some(command) = args.first() else {
+        return Err(usage("missing command"));
+    };
+    match command.as_str() {
+        "help" | "--help" | "-h" => Ok(out.write_all(USAGE.as_bytes())?),
+        "list" => list(out),
+        "stream" | "values" | "state" => {
+            let options = parse_options(args.get(2..).unwrap_or_default())?;
+            let mut rng = generator(args.get(1), options.seed)?;
+            match command.as_str() {
+                "stream" => stream(&mut *rng, options.count, out),
+                "values" => values(&mut *rng, &options, out),
+                _ => state(&mut *rng, options.steps, out),
+            }
+        }
+        _ => Err(usage(format!("unknown command '{command}'"))),
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut stdout = io::stdout().lock();
+    match run(&args, &mut stdout).and_then(|()| Ok(stdout.flush()?)) {
+        Ok(()) => ExitCode::SUCCESS,
+        // The reader, e.g. `head`, has seen enough.
+        Err(Error::Io(io::ErrorKind::BrokenPipe)) => ExitCode::SUCCESS,
+        Err(e @ Error::Usage(_)) => {
+            eprintln!("tc-prng: {e}\n\n{USAGE}");
+            ExitCode::from(2)
+        }
+        Err(e) => {
+            eprintln!("tc-prng: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tc_prng::{prelude::*, Persist};
+
+    fn run_str(args: &str) -> Result<Vec<u8>, Error> {
+        let args: Vec<String> = args.split_whitespace().map(String::from).collect();
+        let mut out = Vec::new();
+        run(&args, &mut out)?;
+        Ok(out)
+    }
+
+    fn text(args: &str) -> String {
+        String::from_utf8(run_str(args).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn stream_matches_library() {
+        let out = run_str("stream xorshiro64 --seed 42 --count 5000").unwrap();
+        let mut rng = xorshiro(42_u64);
+        let expected: Vec<u8> = (0..5000).flat_map(|_| rng.calc().to_le_bytes()).collect();
+        assert_eq!(out, expected);
+
+        let out = run_str("stream romu_trio32 --seed 0x2a --count 3").unwrap();
+        assert_eq!(out.len(), 12);
+        assert!(run_str("stream xorshiro64 --count 0").unwrap().is_empty());
+    }
+
+    #[test]
+    fn values_formats() {
+        let mut rng = xorshiro(42_u64);
+        let a = rng.next::<u8>();
+        let b = rng.next::<u8>();
+        assert_eq!(
+            text("values xorshiro64 --seed 42 --type u8 --count 2"),
+            format!("{a}\n{b}\n")
+        );
+        assert_eq!(
+            text("values xorshiro64 --seed 42 --type u8 --count 2 --format csv"),
+            format!("index,value\n0,{a}\n1,{b}\n")
+        );
+        assert_eq!(
+            text("values xorshiro64 --seed 42 --type u8 --count 1 --format json"),
+            format!("{{\"index\":0,\"value\":{a}}}\n")
+        );
+
+        let c = xorshiro(42_u64).next::<u64>();
+        assert_eq!(
+            text("values xorshiro64 --seed 42 --count 1 --format json"),
+            format!("{{\"index\":0,\"value\":\"{c}\"}}\n")
+        );
+        assert_eq!(text("values wyrand --type normal").lines().count(), 10);
+    }
+
+    #[test]
+    fn state_after_steps() {
+        let mut rng = split_mix(7_u64);
+        for _ in 0..3 {
+            rng.calc();
+        }
+        let out = text("state split_mix64 --seed 7 --steps 3");
+        assert!(out.starts_with("steps: 3\n"), "{out}");
+        assert!(
+            out.contains(&format!("state: {:?}\n", Persist::state(&rng))),
+            "{out}"
+        );
+    }
+
+    #[test]
+    fn usage_errors() {
+        let err = |args| match run_str(args) {
+            Err(Error::Usage(message)) => message,
+            other => panic!("{other:?}"),
+        };
+        assert_eq!(err(""), "missing command");
+        assert_eq!(err("frobnicate"), "unknown command 'frobnicate'");
+        assert!(err("values").starts_with("missing generator"));
+        assert!(err("values mt").starts_with("unknown generator 'mt'"));
+        assert_eq!(err("values mt19937 --type u7"), "unknown type 'u7'");
+        assert_eq!(err("values mt19937 --format xml"), "unknown format 'xml'");
+        assert_eq!(err("values mt19937 --seed"), "--seed: missing value");
+        assert_eq!(err("values mt19937 --seed x"), "--seed: invalid number 'x'");
+        assert_eq!(err("values mt19937 --speed 1"), "unknown option '--speed'");
+    }
+
+    #[test]
+    fn list_names_everything() {
+        let out = text("list");
+        for (name, ..) in GENERATORS {
+            assert!(out.contains(name), "{name}");
+        }
+        assert!(out.contains("normal"));
+        assert!(text("help").starts_with("usage:"));
+    }
+}
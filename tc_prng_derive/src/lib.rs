@@ -0,0 +1,262 @@
+//! `#[derive(Random)]` for `tc_prng`. Use it through `tc_prng::Random` or
+//! `tc::prng::Random`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, spanned::Spanned, Attribute, Data, DeriveInput, Error, Expr,
+    Fields, LitInt, LitStr, Path, Result, Type, WherePredicate,
+};
+
+/// Derives random generation, so `rng.next::<T>()` works for the type with
+/// a generator of any word width.
+///
+/// Fields are drawn in declaration order, each with its type's own
+/// conversion unless an attribute says otherwise:
+///
+/// - `#[random(range = 1..=100)]` draws uniformly from the range, like
+///   `next_range`.
+/// - `#[random(with = path)]` calls `path(rng)`, a function generic over
+///   `G: Prng<u64> + ?Sized` taking `&mut G`.
+///
+/// An enum first draws one value of `0..total_weight` to pick the variant,
+/// then the variant's fields. `#[random(weight = 3)]` on a variant makes it
+/// three times as likely as a variant with the default weight of 1, and
+/// `weight = 0` never picks it.
+///
+/// The generated code names `::tc_prng`, or `::tc::prng` when derived
+/// through `tc::prng::Random`. When the crate is only reachable under
+/// another path, set it with `#[random(crate = "...")]` on the type.
+///
+/// # Examples
+///
+/// ```rust
+/// use tc_prng::{normal::Normal, prelude::*, Prng, Random};
+///
+/// fn height<G: Prng<u64> + ?Sized>(rng: &mut G) -> f64 {
+///     Normal::new(170.0, 8.0).sample(rng)
+/// }
+///
+/// #[derive(Random, Debug)]
+/// struct Monster {
+///     #[random(range = 1..=100)]
+///     level: u32,
+///     #[random(with = height)]
+///     height: f64,
+///     position: [f32; 2],
+///     loot: Loot,
+/// }
+///
+/// #[derive(Random, Debug, PartialEq)]
+/// enum Loot {
+///     #[random(weight = 90)]
+///     Nothing,
+///     #[random(weight = 9)]
+///     Gold(#[random(range = 1..50)] u16),
+///     #[random(weight = 1)]
+///     Sword { sharpness: f32 },
+/// }
+///
+/// let monster: Monster = xorshiro(7_u64).next();
+/// assert!((1..=100).contains(&monster.level));
+/// let loot: Loot = xorshiro(7_u32).next();
+/// ```
+///
+/// Ranges and weights are checked when compiling:
+///
+/// ```compile_fail
+/// #[derive(tc_prng::Random)]
+/// enum Never {
+///     #[random(weight = 0)]
+///     A,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// fn f<G: tc_prng::Prng<u64> + ?Sized>(_: &mut G) -> u8 {
+///     0
+/// }
+///
+/// #[derive(tc_prng::Random)]
+/// struct Both {
+///     #[random(range = 0..10, with = f)]
+///     x: u8,
+/// }
+/// ```
+#[proc_macro_derive(Random, attributes(random))]
+pub fn derive_random(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input, parse_quote!(::tc_prng))
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// [`Random`](derive@Random) naming `::tc::prng` by default, re-exported as
+/// `tc::prng::Random`.
+#[doc(hidden)]
+#[proc_macro_derive(TcRandom, attributes(random))]
+pub fn derive_tc_random(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input, parse_quote!(::tc::prng))
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// How one field is drawn.
+enum Sampler {
+    Word,
+    Range(Expr),
+    With(Path),
+}
+
+fn container_crate(attrs: &[Attribute], mut krate: Path) -> Result<Path> {
+    for attr in attrs.iter().filter(|a| a.path().is_ident("random")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("crate") {
+                krate = meta.value()?.parse::<LitStr>()?.parse()?;
+                Ok(())
+            } else {
+                Err(meta.error("expected `crate = \"...\"`"))
+            }
+        })?;
+    }
+    Ok(krate)
+}
+
+fn field_sampler(attrs: &[Attribute]) -> Result<Sampler> {
+    let mut sampler = Sampler::Word;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("random")) {
+        attr.parse_nested_meta(|meta| {
+            if !matches!(sampler, Sampler::Word) {
+                return Err(meta.error("a field takes only one of `range` and `with`"));
+            }
+            if meta.path.is_ident("range") {
+                sampler = Sampler::Range(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("with") {
+                sampler = Sampler::With(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `range = ...` or `with = ...`"))
+            }
+        })?;
+    }
+    Ok(sampler)
+}
+
+fn variant_weight(attrs: &[Attribute]) -> Result<u64> {
+    let mut weight = 1;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("random")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("weight") {
+                weight = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+                Ok(())
+            } else {
+                Err(meta.error("expected `weight = ...`"))
+            }
+        })?;
+    }
+    Ok(weight)
+}
+
+/// Builds `path { a: .., b: .. }`, `path(.., ..)` or `path`, drawing the
+/// fields in order, and collects the bounds the draws need.
+fn construct(
+    krate: &Path,
+    path: TokenStream2,
+    fields: &Fields,
+    bounds: &mut Vec<WherePredicate>,
+) -> Result<TokenStream2> {
+    let mut values = Vec::new();
+    for field in fields {
+        let ty: &Type = &field.ty;
+        let value = match field_sampler(&field.attrs)? {
+            Sampler::Word => {
+                bounds.push(parse_quote!(#ty: #krate::FromWord<__W>));
+                quote!(<#ty as #krate::FromWord<__W>>::from_word(rng))
+            }
+            Sampler::Range(range) => {
+                bounds.push(parse_quote!(u64: #krate::FromWord<__W>));
+                quote!(#krate::SampleRange::<#ty>::sample(#range, rng))
+            }
+            Sampler::With(with) => {
+                bounds.push(parse_quote!(u64: #krate::FromWord<__W>));
+                quote!(#with(rng))
+            }
+        };
+        values.push(value);
+    }
+
+    Ok(match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|f| &f.ident);
+            quote!(#path { #(#names: #values),* })
+        }
+        Fields::Unnamed(_) => quote!(#path(#(#values),*)),
+        Fields::Unit => path,
+    })
+}
+
+fn expand(input: DeriveInput, krate: Path) -> Result<TokenStream2> {
+    let krate = container_crate(&input.attrs, krate)?;
+    let mut bounds = Vec::new();
+
+    let body = match &input.data {
+        Data::Struct(data) => construct(&krate, quote!(Self), &data.fields, &mut bounds)?,
+        Data::Enum(data) => {
+            let mut arms = Vec::new();
+            let mut total = 0_u64;
+            for variant in &data.variants {
+                let weight = variant_weight(&variant.attrs)?;
+                if weight == 0 {
+                    // Still checked, so mistakes in its fields are reported.
+                    construct(&krate, quote!(), &variant.fields, &mut Vec::new())?;
+                    continue;
+                }
+                total = total
+                    .checked_add(weight)
+                    .ok_or_else(|| Error::new(variant.span(), "total weight overflows u64"))?;
+                let ident = &variant.ident;
+                let value = construct(&krate, quote!(Self::#ident), &variant.fields, &mut bounds)?;
+                arms.push((total, value));
+            }
+            if total == 0 {
+                return Err(Error::new(
+                    input.ident.span(),
+                    "Random needs an enum variant with a non-zero weight",
+                ));
+            }
+            bounds.push(parse_quote!(u64: #krate::FromWord<__W>));
+            // `if pick < end_1 { .. } else if pick < end_2 { .. } else { .. }`
+            let (_, last) = arms.pop().expect("total is not zero");
+            let ends = arms.iter().map(|(end, _)| end);
+            let values = arms.iter().map(|(_, value)| value);
+            quote!({
+                let pick: u64 = #krate::SampleRange::<u64>::sample(0..#total, rng);
+                #(if pick < #ends { #values } else)* { #last }
+            })
+        }
+        Data::Union(_) => {
+            return Err(Error::new(
+                input.ident.span(),
+                "Random cannot be derived for unions",
+            ))
+        }
+    };
+
+    let ident = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let mut generics = input.generics.clone();
+    generics.params.insert(0, parse_quote!(__W));
+    generics.make_where_clause().predicates.extend(bounds);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #krate::FromWord<__W> for #ident #ty_generics #where_clause {
+            fn from_word<__G: #krate::PrngCore<Word = __W> + ?Sized>(rng: &mut __G) -> Self {
+                #body
+            }
+        }
+    })
+}
@@ -68,8 +68,27 @@ where
 mod test {
     use std::thread;
 
+    use tc_prng::check::{self, forall};
+
     use super::*;
 
+    #[derive(Clone, Debug)]
+    enum Op {
+        /// Writes without committing.
+        Write(u64),
+        /// Writes and commits.
+        Publish(u64),
+        Read,
+    }
+
+    fn op() -> check::Gen<Op> {
+        check::weighted(vec![
+            (2, check::any::<u64>().map(Op::Write)),
+            (3, check::any::<u64>().map(Op::Publish)),
+            (3, check::just(Op::Read)),
+        ])
+    }
+
     #[test]
     fn single_thread() {
         let TripleBuffer::<u64>(mut publisher, sub) = TripleBuffer::default();
@@ -82,6 +101,47 @@ mod test {
         assert_eq!(*sub.data(), 42);
     }
 
+    #[test]
+    fn reads_last_published() {
+        let ops = check::any::<u64>().zip(check::vec(op(), 0..=64));
+        forall(ops, |(initial, ops)| {
+            let TripleBuffer::<u64>(mut publisher, sub) = TripleBuffer::new(&initial);
+            let mut published = initial;
+            for op in ops {
+                match op {
+                    Op::Write(v) => *publisher.data() = v,
+                    Op::Publish(v) => {
+                        *publisher.data() = v;
+                        publisher.commit();
+                        published = v;
+                    }
+                    Op::Read => assert_eq!(*sub.data(), published),
+                }
+            }
+            assert_eq!(*sub.data(), published);
+        });
+    }
+
+    #[test]
+    fn reads_are_stable_between_commits() {
+        forall(check::vec(op(), 0..=64), |ops| {
+            let TripleBuffer::<u64>(mut publisher, sub) = TripleBuffer::default();
+            let mut last = *sub.data();
+            for op in ops {
+                match op {
+                    Op::Write(v) => *publisher.data() = v,
+                    Op::Publish(v) => {
+                        *publisher.data() = v;
+                        publisher.commit();
+                        last = *sub.data();
+                        assert_eq!(last, v);
+                    }
+                    Op::Read => assert_eq!(*sub.data(), last),
+                }
+            }
+        });
+    }
+
     #[test]
     fn multi_threaded() {
         let TripleBuffer::<u64>(mut publisher, sub) = TripleBuffer::default();